@@ -0,0 +1,104 @@
+//! `cargo bench` to run everything, or e.g. `cargo bench -- stages/` for one group. To compare against another
+//! version, run `cargo bench -- --save-baseline main` on it first, then `cargo bench -- --baseline main`.
+
+use std::hint::black_box;
+
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion, Throughput};
+use dashify::transform::{collapse_mixed_separators, split_camel_case, split_numbers};
+use dashify::{dashify, dashify_batch, is_dashified, DashifyOptions};
+
+/// Between them these take every path through `dashify`: the clean fast path, camelCase, acronyms and numbers,
+/// special characters, leave-alone names, hidden and compound-extension files and resource forks.
+const NAMES: &[&str] = &[
+    "my-file.txt",
+    "My File.txt",
+    "CamelCaseName.md",
+    "XMLParser.rs",
+    "HTTP2Request.java",
+    "myFile2Go.txt",
+    "file__name--here.txt",
+    "Mixed_Case-Name (1).PDF",
+    "report - final [2].doc",
+    "(weird) name!.txt",
+    "a.-b._c.txt",
+    ".Hidden File",
+    ".bashrc",
+    "__init__.py",
+    "README",
+    "v1.2.3.tar.gz",
+    "Archive.TAR.GZ",
+    "._My File.txt",
+    "file.txt~",
+    "café Menu.pdf",
+];
+
+const CLEAN_NAMES: &[&str] = &["my-file.txt", "report-2024.pdf", ".bashrc", "archive.tar.gz", "readme"];
+
+const UNICODE_NAMES: &[&str] = &["café Menu.pdf", "日本語 ファイル.txt", "Привет Мир.doc", "naïve Résumé.docx"];
+
+fn bench_dashify(c: &mut Criterion) {
+    let options = DashifyOptions::default();
+    let mut group = c.benchmark_group("dashify");
+    for name in NAMES {
+        group.bench_with_input(BenchmarkId::from_parameter(name), name, |b, name| {
+            b.iter(|| dashify(black_box(name), &options))
+        });
+    }
+    group.finish();
+}
+
+fn bench_dashify_batch(c: &mut Criterion) {
+    let options = DashifyOptions::default();
+    let names: Vec<&str> = NAMES.iter().cycle().take(1_000).copied().collect();
+    let mut group = c.benchmark_group("dashify_batch");
+    group.throughput(Throughput::Elements(names.len() as u64));
+    group.bench_function("1000 names", |b| b.iter(|| dashify_batch(black_box(&names), &options)));
+    group.finish();
+}
+
+fn bench_is_dashified(c: &mut Criterion) {
+    let options = DashifyOptions::default();
+    let mut group = c.benchmark_group("is_dashified");
+    for name in CLEAN_NAMES {
+        group.bench_with_input(BenchmarkId::from_parameter(name), name, |b, name| {
+            b.iter(|| is_dashified(black_box(name), &options))
+        });
+    }
+    group.finish();
+}
+
+/// Non-ASCII names are left alone by default, so these measure how quickly `dashify` gives up on them.
+fn bench_leave_alone(c: &mut Criterion) {
+    let options = DashifyOptions::default();
+    let mut group = c.benchmark_group("leave_alone");
+    for name in UNICODE_NAMES {
+        group.bench_with_input(BenchmarkId::from_parameter(name), name, |b, name| {
+            b.iter(|| dashify(black_box(name), &options))
+        });
+    }
+    group.finish();
+}
+
+/// The public stages on their own, each on a 200-character input that gives it plenty to do.
+fn bench_stages(c: &mut Criterion) {
+    let camel_case = "CamelCaseWordWithXMLParserAndHTTPRequest".repeat(5);
+    let numbers = "file2go3d4you5x".repeat(14)[..200].to_string();
+    let separators = "a_-_b--c__d.-e".repeat(15)[..200].to_string();
+    let mut group = c.benchmark_group("stages");
+    group.bench_function("split_camel_case", |b| b.iter(|| split_camel_case(black_box(&camel_case))));
+    group.bench_function("split_numbers", |b| b.iter(|| split_numbers(black_box(&numbers))));
+    group.bench_function("collapse_mixed_separators", |b| {
+        b.iter(|| collapse_mixed_separators(black_box(&separators)))
+    });
+    group.finish();
+}
+
+criterion_group!(
+    benches,
+    bench_dashify,
+    bench_dashify_batch,
+    bench_is_dashified,
+    bench_leave_alone,
+    bench_stages
+);
+criterion_main!(benches);
@@ -0,0 +1,9 @@
+#![no_main]
+
+use dashify::{dashify, DashifyOptions};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    // errors such as EmptyResult are fine, only panics are bugs
+    let _ = dashify(std::str::from_utf8(data).unwrap_or(""), &DashifyOptions::default());
+});
@@ -0,0 +1,12 @@
+#![no_main]
+
+use dashify::{dashify, DashifyOptions};
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| {
+    let options = DashifyOptions {
+        force_dash: true,
+        ..DashifyOptions::default()
+    };
+    let _ = dashify(std::str::from_utf8(data).unwrap_or(""), &options);
+});
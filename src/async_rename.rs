@@ -0,0 +1,106 @@
+use std::borrow::Cow;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use futures_util::stream::{self, Stream, StreamExt};
+use tokio::fs::{self, ReadDir};
+
+use crate::rename::rename_noreplace_keeping_times;
+use crate::{is_resource_fork, CompiledOptions, DashifyError, DashifyOptions, RenameOperation};
+
+/// Dashifies the names of the files in `dir`, and in its subdirectories if `recursive`, yielding each file's
+/// rename as it happens; unchanged files come out with `from == to`. Nothing is overwritten, and an error for one
+/// file or directory doesn't end the stream.
+///
+/// Directories are read with `tokio::fs`, and each rename runs on tokio's blocking pool, so the stream has to be
+/// polled inside a tokio runtime. With an invalid `preserve_patterns` entry the stream yields only that error.
+pub fn dashify_dir_async(
+    dir: &Path,
+    options: Arc<DashifyOptions>,
+    recursive: bool,
+) -> impl Stream<Item = Result<RenameOperation, DashifyError>> {
+    let options = match CompiledOptions::new(Cow::Owned(Arc::unwrap_or_clone(options))) {
+        Ok(options) => Arc::new(options),
+        Err(e) => return stream::iter([Err(e)]).left_stream(),
+    };
+    let walk = Walk {
+        options,
+        recursive,
+        pending: vec![dir.to_path_buf()],
+        current: None,
+    };
+    stream::unfold(walk, |mut walk| async move {
+        let item = walk.next().await?;
+        Some((item, walk))
+    })
+    .right_stream()
+}
+
+struct Walk {
+    options: Arc<CompiledOptions<'static>>,
+    recursive: bool,
+    /// Directories still to be read.
+    pending: Vec<PathBuf>,
+    current: Option<(PathBuf, ReadDir)>,
+}
+
+impl Walk {
+    async fn next(&mut self) -> Option<Result<RenameOperation, DashifyError>> {
+        loop {
+            let (dir, entries) = match &mut self.current {
+                Some(current) => current,
+                None => {
+                    let dir = self.pending.pop()?;
+                    match fs::read_dir(&dir).await {
+                        Ok(entries) => self.current.insert((dir, entries)),
+                        Err(source) => return Some(Err(DashifyError::Io { path: dir, source })),
+                    }
+                }
+            };
+            let entry = match entries.next_entry().await {
+                Ok(Some(entry)) => entry,
+                Ok(None) => {
+                    self.current = None;
+                    continue;
+                }
+                Err(source) => {
+                    let path = dir.clone();
+                    self.current = None;
+                    return Some(Err(DashifyError::Io { path, source }));
+                }
+            };
+            let path = entry.path();
+            let Ok(metadata) = fs::metadata(&path).await else {
+                continue;
+            };
+            if metadata.is_dir() {
+                if self.recursive {
+                    self.pending.push(path);
+                }
+            } else if metadata.is_file() {
+                let file_name = entry.file_name();
+                if !(self.options.skip_resource_forks && is_resource_fork(&file_name.to_string_lossy())) {
+                    return Some(self.rename(path).await);
+                }
+            }
+        }
+    }
+
+    async fn rename(&self, from: PathBuf) -> Result<RenameOperation, DashifyError> {
+        let to = self.options.dashify_path(&from)?;
+        let op = RenameOperation::new(from, to);
+        if !op.is_change() {
+            return Ok(op);
+        }
+        let options = Arc::clone(&self.options);
+        let (from, to) = (op.from.clone(), op.to.clone());
+        // tokio::fs::rename would overwrite; this is the same blocking-pool hop with the no-overwrite rename
+        tokio::task::spawn_blocking(move || rename_noreplace_keeping_times(&from, &to, &options))
+            .await
+            .map_err(|e| DashifyError::Io {
+                path: op.from.clone(),
+                source: e.into(),
+            })??;
+        Ok(op)
+    }
+}
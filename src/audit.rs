@@ -0,0 +1,91 @@
+#[cfg(feature = "serde")]
+use std::fs::{File, OpenOptions};
+#[cfg(feature = "serde")]
+use std::io::Write;
+use std::io;
+use std::path::Path;
+#[cfg(feature = "serde")]
+use std::time::SystemTime;
+
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+
+/// What happened to a file, as recorded in the audit log.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(rename_all = "lowercase"))]
+pub enum LogOp {
+    Rename,
+    Copy,
+    Skip,
+    Error,
+}
+
+/// One attempted operation. Borrows everything so that building an entry for a `NoopLogger` costs nothing.
+#[derive(Debug, Clone, Copy)]
+pub struct LogEntry<'a> {
+    pub op: LogOp,
+    pub from: &'a Path,
+    pub to: Option<&'a Path>,
+    pub error: Option<&'a str>,
+}
+
+pub trait Logger {
+    fn log(&mut self, entry: &LogEntry<'_>) -> io::Result<()>;
+}
+
+/// Discards every entry.
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NoopLogger;
+
+impl Logger for NoopLogger {
+    fn log(&mut self, _entry: &LogEntry<'_>) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// Appends each entry to a file as a JSON object on its own line, flushing after every one so an interrupted run
+/// still leaves a valid log. `RenameSession::load` reads such a log back for undoing. Needs the `serde` feature.
+#[cfg(feature = "serde")]
+#[derive(Debug)]
+pub struct JsonLinesLogger {
+    file: File,
+}
+
+#[cfg(feature = "serde")]
+#[derive(Serialize)]
+struct Line<'a> {
+    ts: String,
+    op: LogOp,
+    from: &'a Path,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    to: Option<&'a Path>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<&'a str>,
+}
+
+#[cfg(feature = "serde")]
+impl JsonLinesLogger {
+    /// Opens `path` for appending, creating it if needed, so successive runs accumulate in one log.
+    pub fn open(path: &Path) -> io::Result<Self> {
+        let file = OpenOptions::new().create(true).append(true).open(path)?;
+        Ok(Self { file })
+    }
+}
+
+#[cfg(feature = "serde")]
+impl Logger for JsonLinesLogger {
+    fn log(&mut self, entry: &LogEntry<'_>) -> io::Result<()> {
+        let line = Line {
+            ts: humantime::format_rfc3339_seconds(SystemTime::now()).to_string(),
+            op: entry.op,
+            from: entry.from,
+            to: entry.to,
+            error: entry.error,
+        };
+        let mut json = serde_json::to_vec(&line)?;
+        json.push(b'\n');
+        // a single write per entry, so concurrent runs appending to the same log don't interleave lines
+        self.file.write_all(&json)?;
+        self.file.flush()
+    }
+}
@@ -0,0 +1,187 @@
+use std::sync::Arc;
+
+#[cfg(feature = "regex-patterns")]
+use regex::Regex;
+
+use crate::{validate_options, CompiledOptions, DashifyError, DashifyOptions, TildeHandling};
+
+/// Builds `DashifyOptions` by chaining setters, starting from the defaults:
+/// `DashifyOptions::builder().force_dash(true).separator('_').build()`.
+///
+/// Each setter sets the `DashifyOptions` field of the same name; those for `Option` fields take the value itself.
+/// `build` checks the result with `validate_options`; `build_compiled` also keeps the compiled `preserve_patterns`.
+#[derive(Debug, Clone, Default)]
+pub struct DashifyOptionsBuilder {
+    options: DashifyOptions,
+}
+
+impl DashifyOptionsBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn build(self) -> Result<DashifyOptions, DashifyError> {
+        validate_options(&self.options)?;
+        Ok(self.options)
+    }
+
+    pub fn build_compiled(self) -> Result<CompiledOptions<'static>, DashifyError> {
+        Ok(validate_options(&self.options)?.into_owned())
+    }
+
+    pub fn force_dash(mut self, force_dash: bool) -> Self {
+        self.options.force_dash = force_dash;
+        self
+    }
+
+    pub fn require_change(mut self, require_change: bool) -> Self {
+        self.options.require_change = require_change;
+        self
+    }
+
+    pub fn normalize_quotes(mut self, normalize_quotes: bool) -> Self {
+        self.options.normalize_quotes = normalize_quotes;
+        self
+    }
+
+    pub fn normalize_dashes(mut self, normalize_dashes: bool) -> Self {
+        self.options.normalize_dashes = normalize_dashes;
+        self
+    }
+
+    pub fn strip_zero_width(mut self, strip_zero_width: bool) -> Self {
+        self.options.strip_zero_width = strip_zero_width;
+        self
+    }
+
+    pub fn min_entropy(mut self, min_entropy: f64) -> Self {
+        self.options.min_entropy = Some(min_entropy);
+        self
+    }
+
+    pub fn preserve_timestamps(mut self, preserve_timestamps: bool) -> Self {
+        self.options.preserve_timestamps = preserve_timestamps;
+        self
+    }
+
+    pub fn filter_fn(mut self, filter: impl Fn(&str) -> bool + Send + Sync + 'static) -> Self {
+        self.options.filter_fn = Some(Arc::new(filter));
+        self
+    }
+
+    /// Adds one leave-alone pattern; can be called repeatedly.
+    #[cfg(feature = "regex-patterns")]
+    pub fn leave_alone_pattern(mut self, pattern: Regex) -> Self {
+        self.options.leave_alone_patterns.push(pattern);
+        self
+    }
+
+    pub fn skip_resource_forks(mut self, skip_resource_forks: bool) -> Self {
+        self.options.skip_resource_forks = skip_resource_forks;
+        self
+    }
+
+    pub fn leave_resource_forks_alone(mut self, leave_resource_forks_alone: bool) -> Self {
+        self.options.leave_resource_forks_alone = leave_resource_forks_alone;
+        self
+    }
+
+    pub fn tilde_handling(mut self, tilde_handling: TildeHandling) -> Self {
+        self.options.tilde_handling = tilde_handling;
+        self
+    }
+
+    pub fn force_ascii(mut self, force_ascii: bool) -> Self {
+        self.options.force_ascii = force_ascii;
+        self
+    }
+
+    pub fn respect_dotenv(mut self, respect_dotenv: bool) -> Self {
+        self.options.respect_dotenv = respect_dotenv;
+        self
+    }
+
+    pub fn strip_noise_words(mut self, strip_noise_words: bool) -> Self {
+        self.options.strip_noise_words = strip_noise_words;
+        self
+    }
+
+    pub fn report_non_ascii_separately(mut self, report_non_ascii_separately: bool) -> Self {
+        self.options.report_non_ascii_separately = report_non_ascii_separately;
+        self
+    }
+
+    pub fn abbreviation_min_length(mut self, abbreviation_min_length: usize) -> Self {
+        self.options.abbreviation_min_length = abbreviation_min_length;
+        self
+    }
+
+    /// Adds one word to the acronym dictionary; can be called repeatedly.
+    pub fn acronym(mut self, word: impl Into<String>) -> Self {
+        self.options.acronym_dictionary.push(word.into());
+        self
+    }
+
+    pub fn preserve_ordinals(mut self, preserve_ordinals: bool) -> Self {
+        self.options.preserve_ordinals = preserve_ordinals;
+        self
+    }
+
+    pub fn preserve_hex(mut self, preserve_hex: bool) -> Self {
+        self.options.preserve_hex = preserve_hex;
+        self
+    }
+
+    pub fn preserve_uuid(mut self, preserve_uuid: bool) -> Self {
+        self.options.preserve_uuid = preserve_uuid;
+        self
+    }
+
+    pub fn preserve_dates(mut self, preserve_dates: bool) -> Self {
+        self.options.preserve_dates = preserve_dates;
+        self
+    }
+
+    pub fn max_stem_words(mut self, max_stem_words: usize) -> Self {
+        self.options.max_stem_words = Some(max_stem_words);
+        self
+    }
+
+    pub fn separator(mut self, separator: char) -> Self {
+        self.options.separator = separator;
+        self
+    }
+
+    pub fn transliterate(mut self, transliterate: bool) -> Self {
+        self.options.transliterate = transliterate;
+        self
+    }
+
+    pub fn strip_diacritics(mut self, strip_diacritics: bool) -> Self {
+        self.options.strip_diacritics = strip_diacritics;
+        self
+    }
+
+    /// Adds one preserve pattern; can be called repeatedly.
+    #[cfg(feature = "regex-patterns")]
+    pub fn preserve_pattern(mut self, pattern: impl Into<String>) -> Self {
+        self.options.preserve_patterns.push(pattern.into());
+        self
+    }
+
+    /// Adds one compound extension to the defaults; can be called repeatedly.
+    pub fn compound_extension(mut self, extension: impl Into<String>) -> Self {
+        self.options.compound_extensions.push(extension.into());
+        self
+    }
+
+    pub fn max_length(mut self, max_length: usize) -> Self {
+        self.options.max_length = Some(max_length);
+        self
+    }
+
+    pub fn slug_mode(mut self, slug_mode: bool) -> Self {
+        self.options.slug_mode = slug_mode;
+        self
+    }
+}
@@ -0,0 +1,129 @@
+//! `DashifyOptions` with their `preserve_patterns` compiled, which is what the pipeline runs on.
+
+use std::borrow::Cow;
+use std::ffi::{OsStr, OsString};
+use std::ops::Deref;
+use std::path::{Path, PathBuf};
+
+#[cfg(feature = "regex-patterns")]
+use regex::Regex;
+
+use crate::rename::{copy_compiled_with_conflict_strategy, rename_compiled_with_conflict_strategy};
+use crate::{CollisionStrategy, DashifyError, DashifyOptions, DashifyResult};
+
+/// `DashifyOptions` checked by `validate_options`, with their `preserve_patterns` compiled once so that names can
+/// be dashified without compiling or checking anything again. The free functions such as `dashify` compile the
+/// patterns on every call, so use this to dashify many names with `preserve_patterns` set. Derefs to the options.
+#[derive(Debug, Clone)]
+pub struct CompiledOptions<'a> {
+    options: Cow<'a, DashifyOptions>,
+    #[cfg(feature = "regex-patterns")]
+    preserve_patterns: Vec<Regex>,
+}
+
+impl<'a> CompiledOptions<'a> {
+    /// Compiles `options.preserve_patterns`, failing with `InvalidPattern` on the first invalid one; unlike
+    /// `validate_options`, doesn't check that the options make sense together.
+    pub(crate) fn new(options: Cow<'a, DashifyOptions>) -> Result<Self, DashifyError> {
+        #[cfg(feature = "regex-patterns")]
+        let preserve_patterns = options.preserve_patterns.iter().map(|pattern| whole_name_pattern(pattern));
+        Ok(CompiledOptions {
+            #[cfg(feature = "regex-patterns")]
+            preserve_patterns: preserve_patterns.collect::<Result<_, _>>()?,
+            options,
+        })
+    }
+
+    pub fn options(&self) -> &DashifyOptions {
+        &self.options
+    }
+
+    pub fn into_owned(self) -> CompiledOptions<'static> {
+        CompiledOptions {
+            options: Cow::Owned(self.options.into_owned()),
+            #[cfg(feature = "regex-patterns")]
+            preserve_patterns: self.preserve_patterns,
+        }
+    }
+
+    /// `dashify` with these options.
+    pub fn dashify(&self, filename: &str) -> Result<String, DashifyError> {
+        self.dashify_with_info(filename).map(|result| result.transformed)
+    }
+
+    /// `dashify_with_info` with these options.
+    pub fn dashify_with_info(&self, filename: &str) -> Result<DashifyResult, DashifyError> {
+        crate::dashify_compiled_with_info(filename, self)
+    }
+
+    /// `dashify_path` with these options.
+    pub fn dashify_path(&self, path: &Path) -> Result<PathBuf, DashifyError> {
+        let file_name = path.file_name().ok_or_else(|| DashifyError::NoFilename {
+            path: path.to_path_buf(),
+        })?;
+        Ok(path.with_file_name(self.dashify_os_str(file_name)?))
+    }
+
+    /// `dashify_dir_name` with these options.
+    pub fn dashify_dir_name(&self, name: &OsStr) -> OsString {
+        crate::dashify_compiled_dir_name(name, self)
+    }
+
+    /// `is_dashified` with these options.
+    pub fn is_dashified(&self, filename: &str) -> bool {
+        crate::is_compiled_dashified(filename, self)
+    }
+
+    /// `DashifyExt::is_dashified` with these options.
+    pub fn is_path_dashified(&self, path: &Path) -> bool {
+        crate::ext::is_path_dashified(path, self)
+    }
+
+    /// `rename_file_with_conflict_strategy` with these options.
+    pub fn rename_file_with_conflict_strategy(
+        &self,
+        path: &Path,
+        strategy: CollisionStrategy,
+    ) -> Result<PathBuf, DashifyError> {
+        rename_compiled_with_conflict_strategy(path, strategy, self)
+    }
+
+    /// `copy_file_with_conflict_strategy` with these options.
+    pub fn copy_file_with_conflict_strategy(
+        &self,
+        path: &Path,
+        dir: &Path,
+        strategy: CollisionStrategy,
+    ) -> Result<PathBuf, DashifyError> {
+        copy_compiled_with_conflict_strategy(path, dir, strategy, self)
+    }
+
+    pub(crate) fn dashify_os_str(&self, filename: &OsStr) -> Result<OsString, DashifyError> {
+        crate::dashify_compiled_os_str(filename, self)
+    }
+
+    #[cfg(feature = "regex-patterns")]
+    pub(crate) fn matches_preserve_pattern(&self, filename: &str) -> bool {
+        self.preserve_patterns.iter().any(|pattern| pattern.is_match(filename))
+    }
+}
+
+impl Deref for CompiledOptions<'_> {
+    type Target = DashifyOptions;
+
+    fn deref(&self) -> &DashifyOptions {
+        &self.options
+    }
+}
+
+// anchored to match the whole filename; the pattern is checked on its own first so errors point into it rather
+// than into the anchoring
+#[cfg(feature = "regex-patterns")]
+fn whole_name_pattern(pattern: &str) -> Result<Regex, DashifyError> {
+    Regex::new(pattern)
+        .and_then(|_| Regex::new(&format!("^(?:{pattern})$")))
+        .map_err(|source| DashifyError::InvalidPattern {
+            pattern: pattern.to_string(),
+            source,
+        })
+}
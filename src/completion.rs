@@ -0,0 +1,545 @@
+//! Hand-maintained shell completion scripts, so library users don't need `clap_complete`.
+//!
+//! `FLAGS` and `SUBCOMMANDS` mirror the CLI's `Args` and have to be kept in sync with it.
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Shell {
+    Bash,
+    Zsh,
+    Fish,
+}
+
+#[derive(Clone, Copy)]
+enum ValueHint {
+    File,
+    Text,
+    Choices(&'static [&'static str]),
+}
+
+struct Flag {
+    short: Option<char>,
+    long: &'static str,
+    value: Option<ValueHint>,
+    help: &'static str,
+}
+
+// `--format json` needs the serde feature
+#[cfg(feature = "serde")]
+const FORMATS: &[&str] = &["text", "json"];
+#[cfg(not(feature = "serde"))]
+const FORMATS: &[&str] = &["text"];
+
+struct Subcommand {
+    name: &'static str,
+    help: &'static str,
+    flags: &'static [Flag],
+    /// What its positional arguments complete to.
+    args: ValueHint,
+}
+
+const HELP: Flag = Flag {
+    short: Some('h'),
+    long: "help",
+    value: None,
+    help: "Print help",
+};
+
+const SUBCOMMANDS: &[Subcommand] = &[
+    Subcommand {
+        name: "check",
+        help: "Report files whose names aren't dashified, without renaming anything",
+        flags: &[
+            Flag {
+                short: Some('r'),
+                long: "recursive",
+                value: None,
+                help: "Check files in subdirectories too",
+            },
+            Flag {
+                short: None,
+                long: "format",
+                value: Some(ValueHint::Choices(FORMATS)),
+                help: "Print 'not clean: PATH' lines or a JSON array",
+            },
+            HELP,
+        ],
+        args: ValueHint::File,
+    },
+    #[cfg(feature = "completions")]
+    Subcommand {
+        name: "completions",
+        help: "Print a completion script for SHELL to stdout",
+        flags: &[HELP],
+        args: ValueHint::Choices(&["bash", "elvish", "fish", "powershell", "zsh"]),
+    },
+    #[cfg(feature = "toml")]
+    Subcommand {
+        name: "config",
+        help: "Manage the .dashify.toml config file",
+        flags: &[HELP],
+        args: ValueHint::Choices(&["init"]),
+    },
+];
+
+const FLAGS: &[Flag] = &[
+    Flag {
+        short: Some('r'),
+        long: "recursive",
+        value: None,
+        help: "Recursively process files in subdirectories",
+    },
+    Flag {
+        short: None,
+        long: "normalize-dirs",
+        value: None,
+        help: "With --recursive, rename the subdirectories too, deepest first",
+    },
+    Flag {
+        short: None,
+        long: "report",
+        value: Some(ValueHint::File),
+        help: "Write an HTML report of all rename operations",
+    },
+    Flag {
+        short: None,
+        long: "list",
+        value: None,
+        help: "List files that would be renamed without renaming them",
+    },
+    Flag {
+        short: None,
+        long: "count",
+        value: None,
+        help: "With --list, print only the number of files that would be renamed",
+    },
+    Flag {
+        short: None,
+        long: "check",
+        value: None,
+        help: "Exit non-zero and list the files if any would be renamed",
+    },
+    Flag {
+        short: None,
+        long: "stats",
+        value: None,
+        help: "Print how many files were renamed, unchanged, skipped and failed to stderr when done",
+    },
+    Flag {
+        short: None,
+        long: "format",
+        value: Some(ValueHint::Choices(FORMATS)),
+        help: "Print the renames, and the --stats counts, as text or as one JSON object on stdout",
+    },
+    Flag {
+        short: None,
+        long: "summary",
+        value: None,
+        help: "Like --stats, but also print how many bytes the new names saved",
+    },
+    Flag {
+        short: None,
+        long: "output-dir",
+        value: Some(ValueHint::File),
+        help: "Copy files to DIR under their new names, leaving the originals untouched",
+    },
+    #[cfg(feature = "serde")]
+    Flag {
+        short: None,
+        long: "save-session",
+        value: Some(ValueHint::File),
+        help: "Save the renames made to FILE so they can be undone with --undo",
+    },
+    #[cfg(feature = "serde")]
+    Flag {
+        short: None,
+        long: "log-file",
+        value: Some(ValueHint::File),
+        help: "Append a JSON Lines record of every rename, skip and error to PATH",
+    },
+    #[cfg(feature = "serde")]
+    Flag {
+        short: None,
+        long: "undo",
+        value: Some(ValueHint::File),
+        help: "Undo the renames saved in FILE by --save-session or --log-file",
+    },
+    Flag {
+        short: None,
+        long: "stdin",
+        value: None,
+        help: "Read names from stdin and print their dashified forms without touching any files",
+    },
+    Flag {
+        short: Some('0'),
+        long: "null",
+        value: None,
+        help: "With --stdin, read NUL-separated names, as from find -print0",
+    },
+    #[cfg(feature = "watch")]
+    Flag {
+        short: None,
+        long: "watch",
+        value: None,
+        help: "Keep running and dashify new files as they appear in the given directories",
+    },
+    Flag {
+        short: Some('n'),
+        long: "dry-run",
+        value: None,
+        help: "Show what would be renamed without renaming anything",
+    },
+    Flag {
+        short: None,
+        long: "atomic",
+        value: None,
+        help: "Rename all the files or, if any rename fails, none of them",
+    },
+    Flag {
+        short: Some('j'),
+        long: "parallel",
+        // the thread count is optional, so don't make the shell insist on one
+        value: None,
+        help: "Rename on N threads, or one per CPU if N is left out",
+    },
+    Flag {
+        short: Some('i'),
+        long: "interactive",
+        value: None,
+        help: "Ask before each rename",
+    },
+    Flag {
+        short: None,
+        long: "conflict-strategy",
+        value: Some(ValueHint::Choices(&["skip", "overwrite", "number", "error"])),
+        help: "What to do when the new name is taken: skip, overwrite, number or error",
+    },
+    Flag {
+        short: Some('v'),
+        long: "verbose",
+        value: None,
+        help: "Print each rename, skip and error as it happens",
+    },
+    Flag {
+        short: None,
+        long: "no-env-file",
+        value: None,
+        help: "Ignore DASHIFY_* settings in .env files",
+    },
+    #[cfg(feature = "toml")]
+    Flag {
+        short: None,
+        long: "no-config",
+        value: None,
+        help: "Ignore .dashify.toml in the current directory and the home directory",
+    },
+    Flag {
+        short: None,
+        long: "force-dash",
+        value: None,
+        help: "Replace underscores with the separator too",
+    },
+    Flag {
+        short: None,
+        long: "separator",
+        value: Some(ValueHint::Text),
+        help: "Character to join words with [default: -]",
+    },
+    Flag {
+        short: None,
+        long: "force-ascii",
+        value: None,
+        help: "Strip non-ASCII characters instead of leaving such names alone",
+    },
+    Flag {
+        short: None,
+        long: "transliterate",
+        value: None,
+        help: "Transliterate non-ASCII names to ASCII, e.g. café to cafe, instead of leaving them alone",
+    },
+    Flag {
+        short: None,
+        long: "strip-diacritics",
+        value: None,
+        help: "Remove accents from letters, e.g. naïve to naive",
+    },
+    Flag {
+        short: None,
+        long: "strip-noise-words",
+        value: None,
+        help: "Remove download noise such as 'Copy of' and '(2)'",
+    },
+    #[cfg(feature = "regex-patterns")]
+    Flag {
+        short: None,
+        long: "preserve",
+        value: Some(ValueHint::Text),
+        help: "Leave alone names that this regex matches in full; repeatable",
+    },
+    Flag {
+        short: None,
+        long: "compound-ext",
+        value: Some(ValueHint::Text),
+        help: "Keep this multi-part extension whole, e.g. d.ts; repeatable",
+    },
+    Flag {
+        short: None,
+        long: "acronym",
+        value: Some(ValueHint::Text),
+        help: "Don't split this word at its capitals, e.g. GraphQL; repeatable",
+    },
+    Flag {
+        short: None,
+        long: "preserve-timestamps",
+        value: None,
+        help: "Keep the original access and modification times",
+    },
+    HELP,
+    Flag {
+        short: Some('V'),
+        long: "version",
+        value: None,
+        help: "Print version",
+    },
+];
+
+pub fn generate_completion_script(shell: Shell, binary_name: &str) -> String {
+    match shell {
+        Shell::Bash => bash(binary_name),
+        Shell::Zsh => zsh(binary_name),
+        Shell::Fish => fish(binary_name),
+    }
+}
+
+fn function_name(binary_name: &str) -> String {
+    let sanitized: String = binary_name
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect();
+    format!("_{sanitized}")
+}
+
+
+fn bash(binary_name: &str) -> String {
+    let function = function_name(binary_name);
+    let names: Vec<&str> = SUBCOMMANDS.iter().map(|subcommand| subcommand.name).collect();
+    let mut subcommand_cases = String::new();
+    for subcommand in SUBCOMMANDS {
+        let body = bash_body(subcommand.flags, &bash_args(subcommand.args), "            ");
+        subcommand_cases.push_str(&format!("        {})\n{body}            ;;\n", subcommand.name));
+    }
+    let args = format!(
+        "COMPREPLY=( $(compgen -W \"{}\" -- \"$cur\") $(compgen -f -- \"$cur\") )",
+        names.join(" ")
+    );
+
+    format!(
+        r#"{function}() {{
+    local cur prev cmd i
+    COMPREPLY=()
+    cur="${{COMP_WORDS[COMP_CWORD]}}"
+    prev="${{COMP_WORDS[COMP_CWORD-1]}}"
+
+    for ((i = 1; i < COMP_CWORD; i++)); do
+        case "${{COMP_WORDS[i]}}" in
+            {subcommands})
+                cmd="${{COMP_WORDS[i]}}"
+                break
+                ;;
+        esac
+    done
+
+    case "$cmd" in
+{subcommand_cases}    esac
+
+{body}}}
+
+complete -o filenames -F {function} {binary_name}
+"#,
+        subcommands = names.join("|"),
+        body = bash_body(FLAGS, &args, "    "),
+    )
+}
+
+// completes the values of `flags`, then the flags themselves, then the positional arguments with `args`
+fn bash_body(flags: &[Flag], args: &str, indent: &str) -> String {
+    let words: Vec<String> = flags
+        .iter()
+        .flat_map(|flag| {
+            let mut words = vec![format!("--{}", flag.long)];
+            if let Some(short) = flag.short {
+                words.push(format!("-{short}"));
+            }
+            words
+        })
+        .collect();
+
+    let mut value_cases = String::new();
+    for flag in flags {
+        let Some(value) = flag.value else { continue };
+        let mut patterns = vec![format!("--{}", flag.long)];
+        if let Some(short) = flag.short {
+            patterns.push(format!("-{short}"));
+        }
+        value_cases.push_str(&format!(
+            "    {})\n        {}\n        return 0\n        ;;\n",
+            patterns.join("|"),
+            bash_args(value)
+        ));
+    }
+
+    if !value_cases.is_empty() {
+        value_cases = format!("case \"$prev\" in\n{value_cases}esac\n\n");
+    }
+    let body = format!(
+        r#"{value_cases}if [[ "$cur" == -* ]]; then
+    COMPREPLY=( $(compgen -W "{words}" -- "$cur") )
+    return 0
+fi
+
+{args}
+return 0
+"#,
+        words = words.join(" "),
+    );
+    body.lines()
+        .map(|line| if line.is_empty() { "\n".to_string() } else { format!("{indent}{line}\n") })
+        .collect()
+}
+
+fn bash_args(value: ValueHint) -> String {
+    match value {
+        ValueHint::File => "COMPREPLY=( $(compgen -f -- \"$cur\") )".to_string(),
+        ValueHint::Text => "COMPREPLY=()".to_string(),
+        ValueHint::Choices(choices) => format!("COMPREPLY=( $(compgen -W \"{}\" -- \"$cur\") )", choices.join(" ")),
+    }
+}
+
+fn zsh(binary_name: &str) -> String {
+    let function = function_name(binary_name);
+    let escape = |help: &str| help.replace('\'', "'\\''").replace(':', "\\:");
+    let subcommands: Vec<String> = SUBCOMMANDS
+        .iter()
+        .map(|subcommand| format!("{}\\:\"{}\"", subcommand.name, escape(subcommand.help)))
+        .collect();
+    let mut subcommand_cases = String::new();
+    for subcommand in SUBCOMMANDS {
+        let mut specs = zsh_specs(subcommand.flags);
+        specs.push(match subcommand.args {
+            ValueHint::File => "'*:path:_files'".to_string(),
+            ValueHint::Text => "'*:value: '".to_string(),
+            ValueHint::Choices(choices) => format!("'*:{}:({})'", subcommand.name, choices.join(" ")),
+        });
+        let specs = specs.join(" \\\n                        ");
+        subcommand_cases.push_str(&format!("                {})\n", subcommand.name));
+        subcommand_cases.push_str(&format!("                    _arguments -s \\\n                        {specs}\n"));
+        subcommand_cases.push_str("                    ;;\n");
+    }
+    let mut specs = zsh_specs(FLAGS);
+    specs.push("'1: :->first'".to_string());
+    specs.push("'*::arg:->args'".to_string());
+
+    format!(
+        r#"#compdef {binary_name}
+
+{function}() {{
+    local context state state_descr line
+    typeset -A opt_args
+    _arguments -s -C \
+        {specs}
+    case $state in
+        first)
+            _alternative 'subcommands:subcommand:(({subcommands}))' 'paths:path:_files'
+            ;;
+        args)
+            case $line[1] in
+{subcommand_cases}                *)
+                    _files
+                    ;;
+            esac
+            ;;
+    esac
+}}
+
+{function} "$@"
+"#,
+        specs = specs.join(" \\\n        "),
+        subcommands = subcommands.join(" "),
+    )
+}
+
+fn zsh_specs(flags: &[Flag]) -> Vec<String> {
+    let mut specs = Vec::new();
+    for flag in flags {
+        let help = flag
+            .help
+            .replace('\'', "'\\''")
+            .replace('[', "\\[")
+            .replace(']', "\\]")
+            .replace(':', "\\:");
+        let action = match flag.value {
+            Some(ValueHint::File) => format!(":{}:_files", flag.long),
+            Some(ValueHint::Text) => format!(":{}: ", flag.long),
+            Some(ValueHint::Choices(choices)) => format!(":{}:({})", flag.long, choices.join(" ")),
+            None => String::new(),
+        };
+        specs.push(match flag.short {
+            Some(short) => format!(
+                "'(-{short} --{long})'{{-{short},--{long}}}'[{help}]{action}'",
+                long = flag.long
+            ),
+            None => format!("'--{}[{help}]{action}'", flag.long),
+        });
+    }
+    specs
+}
+
+fn fish(binary_name: &str) -> String {
+    let names: Vec<&str> = SUBCOMMANDS.iter().map(|subcommand| subcommand.name).collect();
+    let top_level = format!("not __fish_seen_subcommand_from {}", names.join(" "));
+    let escape = |help: &str| help.replace('\'', "\\'");
+    let mut script = fish_flags(binary_name, &top_level, FLAGS);
+    for subcommand in SUBCOMMANDS {
+        script.push_str(&format!(
+            "complete -c {binary_name} -n '{top_level}' -a {} -d '{}'\n",
+            subcommand.name,
+            escape(subcommand.help)
+        ));
+    }
+    for subcommand in SUBCOMMANDS {
+        let condition = format!("__fish_seen_subcommand_from {}", subcommand.name);
+        script.push_str(&fish_flags(binary_name, &condition, subcommand.flags));
+        if let ValueHint::Choices(choices) = subcommand.args {
+            script.push_str(&format!("complete -c {binary_name} -n '{condition}' -f -a '{}'\n", choices.join(" ")));
+        }
+    }
+    let takes_files: Vec<&str> = SUBCOMMANDS
+        .iter()
+        .filter(|subcommand| matches!(subcommand.args, ValueHint::File))
+        .map(|subcommand| subcommand.name)
+        .collect();
+    script.push_str(&format!(
+        "complete -c {binary_name} -n '{top_level}; or __fish_seen_subcommand_from {}' -F\n",
+        takes_files.join(" ")
+    ));
+    script
+}
+
+fn fish_flags(binary_name: &str, condition: &str, flags: &[Flag]) -> String {
+    let mut script = String::new();
+    for flag in flags {
+        let mut line = format!("complete -c {binary_name} -n '{condition}'");
+        if let Some(short) = flag.short {
+            line.push_str(&format!(" -s {short}"));
+        }
+        line.push_str(&format!(" -l {}", flag.long));
+        match flag.value {
+            Some(ValueHint::File) => line.push_str(" -r -F"),
+            Some(ValueHint::Text) => line.push_str(" -r -f"),
+            Some(ValueHint::Choices(choices)) => line.push_str(&format!(" -r -f -a '{}'", choices.join(" "))),
+            None => {}
+        }
+        line.push_str(&format!(" -d '{}'\n", flag.help.replace('\'', "\\'")));
+        script.push_str(&line);
+    }
+    script
+}
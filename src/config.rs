@@ -0,0 +1,215 @@
+use std::env;
+use std::fmt;
+#[cfg(feature = "toml")]
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::{DashifyOptions, TildeHandling};
+
+/// Settings that couldn't be loaded, e.g. a malformed TOML file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConfigError {
+    /// The file the settings came from, when known.
+    pub path: Option<PathBuf>,
+    /// The 1-based line the problem is on, when known.
+    pub line: Option<usize>,
+    pub message: String,
+}
+
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (&self.path, self.line) {
+            (Some(path), Some(line)) => write!(f, "{}:{line}: {}", path.display(), self.message),
+            (Some(path), None) => write!(f, "{}: {}", path.display(), self.message),
+            (None, Some(line)) => write!(f, "line {line}: {}", self.message),
+            (None, None) => f.write_str(&self.message),
+        }
+    }
+}
+
+impl std::error::Error for ConfigError {}
+
+impl DashifyOptions {
+    /// Parses options from TOML, with the same keys as the `serde` representation, e.g. `force_dash = true`.
+    /// Missing keys keep their defaults; unknown keys and invalid combinations (see `validate_options`) fail.
+    #[cfg(feature = "toml")]
+    pub fn from_toml(s: &str) -> Result<Self, ConfigError> {
+        let options: DashifyOptions = toml::from_str(s).map_err(|e| ConfigError {
+            path: None,
+            line: e.span().map(|span| s[..span.start].matches('\n').count() + 1),
+            message: e.message().to_string(),
+        })?;
+        crate::validate_options(&options).map_err(|e| ConfigError {
+            path: None,
+            line: None,
+            message: e.to_string(),
+        })?;
+        Ok(options)
+    }
+
+    /// Reads options from a TOML file, see `from_toml`. Errors carry `path`.
+    #[cfg(feature = "toml")]
+    pub fn from_config_file(path: &Path) -> Result<Self, ConfigError> {
+        let contents = fs::read_to_string(path).map_err(|e| ConfigError {
+            path: Some(path.to_path_buf()),
+            line: None,
+            message: e.to_string(),
+        })?;
+        Self::from_toml(&contents).map_err(|e| ConfigError {
+            path: Some(path.to_path_buf()),
+            ..e
+        })
+    }
+
+    /// Default options, plus any `DASHIFY_*` settings from the environment. Invalid settings are ignored; use
+    /// `apply_env` to find out about them.
+    pub fn from_env() -> Self {
+        let mut options = DashifyOptions::default();
+        options.apply_env();
+        options
+    }
+
+    /// Applies the `DASHIFY_*` settings from the environment on top of the current ones, returning the invalid
+    /// ones, which are left out.
+    pub fn apply_env(&mut self) -> Vec<ConfigError> {
+        let mut errors = Vec::new();
+        for (key, value) in env::vars_os() {
+            let (Some(key), Some(value)) = (key.to_str(), value.to_str()) else {
+                continue;
+            };
+            if let Err(message) = self.apply_var(key, value) {
+                errors.push(ConfigError {
+                    path: None,
+                    line: None,
+                    message,
+                });
+            }
+        }
+        errors
+    }
+
+    /// Default options, plus any `DASHIFY_*` settings from the nearest `.env` file at or above `dir`. Invalid
+    /// settings are ignored; use `apply_dotenv` to find out about them.
+    pub fn from_project_dir(dir: &Path) -> Self {
+        let mut options = DashifyOptions::default();
+        if options.respect_dotenv {
+            options.apply_dotenv(dir);
+        }
+        options
+    }
+
+    /// Applies the `DASHIFY_*` settings from the nearest `.env` file at or above `dir`, on top of the current ones.
+    /// Returns the problems with the file: invalid settings, which are left out, or the file being unreadable.
+    pub fn apply_dotenv(&mut self, dir: &Path) -> Vec<ConfigError> {
+        let dir = dir.canonicalize().unwrap_or_else(|_| dir.to_path_buf());
+        let Some(env_file) = dir.ancestors().map(|ancestor| ancestor.join(".env")).find(|path| path.is_file()) else {
+            return Vec::new();
+        };
+        let error = |message: String| ConfigError {
+            path: Some(env_file.clone()),
+            line: None,
+            message,
+        };
+        let vars = match dotenvy::from_path_iter(&env_file) {
+            Ok(vars) => vars,
+            Err(e) => return vec![error(format!("could not read it: {e}"))],
+        };
+        let mut errors = Vec::new();
+        for item in vars {
+            match item {
+                Ok((key, value)) => {
+                    if let Err(message) = self.apply_var(&key, &value) {
+                        errors.push(error(message));
+                    }
+                }
+                Err(e) => errors.push(error(e.to_string())),
+            }
+        }
+        errors
+    }
+
+    /// Applies a single `DASHIFY_*` setting; other keys are ignored.
+    fn apply_var(&mut self, key: &str, value: &str) -> Result<(), String> {
+        let Some(name) = key.strip_prefix("DASHIFY_") else {
+            return Ok(());
+        };
+        match name {
+            "FORCE_DASH" => self.force_dash = parse_bool(key, value)?,
+            "REQUIRE_CHANGE" => self.require_change = parse_bool(key, value)?,
+            "NORMALIZE_QUOTES" | "HANDLE_UNICODE_QUOTES" => self.normalize_quotes = parse_bool(key, value)?,
+            "NORMALIZE_DASHES" => self.normalize_dashes = parse_bool(key, value)?,
+            "STRIP_ZERO_WIDTH" => self.strip_zero_width = parse_bool(key, value)?,
+            "MIN_ENTROPY" => {
+                self.min_entropy = Some(value.parse().map_err(|_| format!("{key}: expected a number, got '{value}'"))?)
+            }
+            "PRESERVE_TIMESTAMPS" => self.preserve_timestamps = parse_bool(key, value)?,
+            "SKIP_RESOURCE_FORKS" => self.skip_resource_forks = parse_bool(key, value)?,
+            "LEAVE_RESOURCE_FORKS_ALONE" => self.leave_resource_forks_alone = parse_bool(key, value)?,
+            "TILDE_HANDLING" => {
+                self.tilde_handling = match value.to_lowercase().as_str() {
+                    "preserve" => TildeHandling::Preserve,
+                    "remove" => TildeHandling::Remove,
+                    "to-hyphen" | "to_hyphen" | "hyphen" => TildeHandling::ToHyphen,
+                    _ => return Err(format!("{key}: expected preserve, remove or to-hyphen, got '{value}'")),
+                }
+            }
+            "FORCE_ASCII" => self.force_ascii = parse_bool(key, value)?,
+            "STRIP_NOISE_WORDS" => self.strip_noise_words = parse_bool(key, value)?,
+            "REPORT_NON_ASCII_SEPARATELY" => self.report_non_ascii_separately = parse_bool(key, value)?,
+            "ABBREVIATION_MIN_LENGTH" => {
+                self.abbreviation_min_length =
+                    value.parse().map_err(|_| format!("{key}: expected a whole number, got '{value}'"))?
+            }
+            "PRESERVE_ORDINALS" => self.preserve_ordinals = parse_bool(key, value)?,
+            "PRESERVE_HEX" => self.preserve_hex = parse_bool(key, value)?,
+            "PRESERVE_UUID" => self.preserve_uuid = parse_bool(key, value)?,
+            "PRESERVE_DATES" => self.preserve_dates = parse_bool(key, value)?,
+            "MAX_STEM_WORDS" => {
+                self.max_stem_words =
+                    Some(value.parse().map_err(|_| format!("{key}: expected a whole number, got '{value}'"))?)
+            }
+            "MAX_LENGTH" => {
+                self.max_length =
+                    Some(value.parse().map_err(|_| format!("{key}: expected a whole number, got '{value}'"))?)
+            }
+            "SEPARATOR" => {
+                let mut chars = value.chars();
+                self.separator = match (chars.next(), chars.next()) {
+                    (Some(c), None) => c,
+                    _ => return Err(format!("{key}: expected a single character, got '{value}'")),
+                }
+            }
+            "TRANSLITERATE" => self.transliterate = parse_bool(key, value)?,
+            "STRIP_DIACRITICS" => self.strip_diacritics = parse_bool(key, value)?,
+            "COMPOUND_EXTENSIONS" => self
+                .compound_extensions
+                .extend(value.split(',').map(str::trim).filter(|extension| !extension.is_empty()).map(String::from)),
+            "ACRONYM_DICTIONARY" => self
+                .acronym_dictionary
+                .extend(value.split(',').map(str::trim).filter(|word| !word.is_empty()).map(String::from)),
+            "SLUG_MODE" => self.slug_mode = parse_bool(key, value)?,
+            _ => return Err(format!("unknown setting {key}")),
+        }
+        Ok(())
+    }
+}
+
+fn parse_bool(key: &str, value: &str) -> Result<bool, String> {
+    match value.to_lowercase().as_str() {
+        "1" | "true" | "yes" | "on" => Ok(true),
+        "0" | "false" | "no" | "off" | "" => Ok(false),
+        _ => Err(format!("{key}: expected a boolean, got '{value}'")),
+    }
+}
+
+/// The config file to use: `.dashify.toml` in `dir` if there is one, otherwise `~/.dashify.toml`. Only the first
+/// one found is used; the two aren't merged.
+pub fn find_config_file(dir: &Path) -> Option<PathBuf> {
+    let local = dir.join(CONFIG_FILE_NAME);
+    if local.is_file() {
+        return Some(local);
+    }
+    dirs::home_dir().map(|home| home.join(CONFIG_FILE_NAME)).filter(|path| path.is_file())
+}
+
+pub const CONFIG_FILE_NAME: &str = ".dashify.toml";
@@ -0,0 +1,118 @@
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum NamingConvention {
+    LowerKebab,
+    LowerSnake,
+    UpperKebab,
+    UpperSnake,
+    CamelCase,
+    PascalCase,
+    TitleCase,
+    AllCaps,
+    Mixed,
+    Unknown,
+}
+
+/// Guesses the naming convention of `name` from its separators and letter case.
+///
+/// A single lowercase word is reported as `LowerKebab`, the form `dashify` produces.
+pub fn detect_naming_convention(name: &str) -> NamingConvention {
+    if !name.chars().any(|c| c.is_alphabetic()) {
+        return NamingConvention::Unknown;
+    }
+    let has_dash = name.contains('-');
+    let has_underscore = name.contains('_');
+    let has_space = name.contains(' ');
+    let has_upper = name.chars().any(|c| c.is_uppercase());
+    let has_lower = name.chars().any(|c| c.is_lowercase());
+
+    if has_space {
+        let is_title = !has_dash
+            && !has_underscore
+            && name.split(' ').filter(|word| !word.is_empty()).all(|word| {
+                let mut chars = word.chars();
+                chars.next().is_some_and(|c| c.is_uppercase() || c.is_ascii_digit())
+                    && chars.all(|c| !c.is_uppercase())
+            });
+        return if is_title { NamingConvention::TitleCase } else { NamingConvention::Mixed };
+    }
+
+    match (has_dash, has_underscore) {
+        (true, true) => NamingConvention::Mixed,
+        (true, false) => match (has_upper, has_lower) {
+            (false, _) => NamingConvention::LowerKebab,
+            (true, false) => NamingConvention::UpperKebab,
+            (true, true) => NamingConvention::Mixed,
+        },
+        (false, true) => match (has_upper, has_lower) {
+            (false, _) => NamingConvention::LowerSnake,
+            (true, false) => NamingConvention::UpperSnake,
+            (true, true) => NamingConvention::Mixed,
+        },
+        (false, false) => match (has_upper, has_lower) {
+            (false, _) => NamingConvention::LowerKebab,
+            (true, false) => NamingConvention::AllCaps,
+            (true, true) => {
+                if name.starts_with(|c: char| c.is_uppercase()) {
+                    NamingConvention::PascalCase
+                } else {
+                    NamingConvention::CamelCase
+                }
+            }
+        },
+    }
+}
+
+/// Re-cases `name` from one convention to another, using the source convention to find word boundaries.
+///
+/// `Mixed` and `Unknown` as a target leave `name` unchanged.
+pub fn convert_naming_convention(name: &str, from: NamingConvention, to: NamingConvention) -> String {
+    let words = split_words(name, from);
+    match to {
+        NamingConvention::LowerKebab => join_mapped(&words, "-", str::to_lowercase),
+        NamingConvention::LowerSnake => join_mapped(&words, "_", str::to_lowercase),
+        NamingConvention::UpperKebab => join_mapped(&words, "-", str::to_uppercase),
+        NamingConvention::UpperSnake => join_mapped(&words, "_", str::to_uppercase),
+        NamingConvention::AllCaps => join_mapped(&words, "", str::to_uppercase),
+        NamingConvention::PascalCase => join_mapped(&words, "", capitalize),
+        NamingConvention::TitleCase => join_mapped(&words, " ", capitalize),
+        NamingConvention::CamelCase => words
+            .iter()
+            .enumerate()
+            .map(|(i, word)| if i == 0 { word.to_lowercase() } else { capitalize(word) })
+            .collect(),
+        NamingConvention::Mixed | NamingConvention::Unknown => name.to_string(),
+    }
+}
+
+fn split_words(name: &str, convention: NamingConvention) -> Vec<String> {
+    let words: Vec<String> = match convention {
+        NamingConvention::LowerKebab | NamingConvention::UpperKebab => {
+            name.split('-').map(str::to_string).collect()
+        }
+        NamingConvention::LowerSnake | NamingConvention::UpperSnake => {
+            name.split('_').map(str::to_string).collect()
+        }
+        NamingConvention::TitleCase => name.split(' ').map(str::to_string).collect(),
+        NamingConvention::AllCaps => vec![name.to_string()],
+        NamingConvention::CamelCase | NamingConvention::PascalCase => {
+            crate::transform::split_camel_case(name).split('-').map(str::to_string).collect()
+        }
+        NamingConvention::Mixed | NamingConvention::Unknown => crate::transform::split_camel_case(name)
+            .split(['-', '_', ' '])
+            .map(str::to_string)
+            .collect(),
+    };
+    words.into_iter().filter(|word| !word.is_empty()).collect()
+}
+
+fn join_mapped(words: &[String], separator: &str, f: impl Fn(&str) -> String) -> String {
+    words.iter().map(|word| f(word)).collect::<Vec<_>>().join(separator)
+}
+
+pub(crate) fn capitalize(word: &str) -> String {
+    let mut chars = word.chars();
+    match chars.next() {
+        Some(first) => first.to_uppercase().chain(chars.flat_map(char::to_lowercase)).collect(),
+        None => String::new(),
+    }
+}
@@ -0,0 +1,116 @@
+use crate::{dashify, dashify_decoded, DashifyOptions};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Encoding {
+    Utf8,
+    Latin1,
+    Cp1252,
+    Unknown,
+}
+
+// Windows-1252 code points for bytes 0x80..=0x9F; `None` marks the five undefined bytes.
+const CP1252_HIGH: [Option<char>; 32] = [
+    Some('\u{20AC}'),
+    None,
+    Some('\u{201A}'),
+    Some('\u{0192}'),
+    Some('\u{201E}'),
+    Some('\u{2026}'),
+    Some('\u{2020}'),
+    Some('\u{2021}'),
+    Some('\u{02C6}'),
+    Some('\u{2030}'),
+    Some('\u{0160}'),
+    Some('\u{2039}'),
+    Some('\u{0152}'),
+    None,
+    Some('\u{017D}'),
+    None,
+    None,
+    Some('\u{2018}'),
+    Some('\u{2019}'),
+    Some('\u{201C}'),
+    Some('\u{201D}'),
+    Some('\u{2022}'),
+    Some('\u{2013}'),
+    Some('\u{2014}'),
+    Some('\u{02DC}'),
+    Some('\u{2122}'),
+    Some('\u{0161}'),
+    Some('\u{203A}'),
+    Some('\u{0153}'),
+    None,
+    Some('\u{017E}'),
+    Some('\u{0178}'),
+];
+
+/// Guesses how a raw filename is encoded.
+///
+/// Valid UTF-8 (with or without a BOM) wins. Otherwise bytes in 0x80..=0x9F, which are control characters in
+/// Latin-1 but printable in Windows-1252, decide between the two.
+pub fn detect_encoding(bytes: &[u8]) -> Encoding {
+    if bytes.starts_with(&[0xEF, 0xBB, 0xBF]) || std::str::from_utf8(bytes).is_ok() {
+        return Encoding::Utf8;
+    }
+    let c1_controls: Vec<u8> = bytes.iter().copied().filter(|b| (0x80..=0x9F).contains(b)).collect();
+    if c1_controls.is_empty() {
+        Encoding::Latin1
+    } else if c1_controls.iter().all(|&b| CP1252_HIGH[usize::from(b - 0x80)].is_some()) {
+        Encoding::Cp1252
+    } else {
+        Encoding::Unknown
+    }
+}
+
+/// Dashifies a raw filename in the given encoding, returning it unchanged if it can't be decoded, `dashify`
+/// fails on it, or the result can't be encoded back.
+///
+/// UTF-8 names are handled exactly as by `dashify`. Latin-1 and Windows-1252 names aren't left alone for having
+/// letters outside ASCII, since those are what the encodings are for, so Latin-1 `b"Caf\xe9 Menu.txt"` becomes
+/// `b"caf\xe9-menu.txt"`.
+pub fn dashify_bytes(name: &[u8], encoding: Encoding, options: &DashifyOptions) -> Vec<u8> {
+    let Some(decoded) = decode(name, encoding) else {
+        return name.to_vec();
+    };
+    let dashified = match encoding {
+        Encoding::Utf8 => dashify(&decoded, options),
+        _ => dashify_decoded(&decoded, options),
+    };
+    dashified
+        .ok()
+        .and_then(|dashified| encode(&dashified, encoding))
+        .unwrap_or_else(|| name.to_vec())
+}
+
+fn decode(bytes: &[u8], encoding: Encoding) -> Option<String> {
+    match encoding {
+        Encoding::Utf8 => String::from_utf8(bytes.to_vec()).ok(),
+        Encoding::Latin1 => Some(bytes.iter().map(|&b| char::from(b)).collect()),
+        Encoding::Cp1252 => bytes
+            .iter()
+            .map(|&b| match b {
+                0x80..=0x9F => CP1252_HIGH[usize::from(b - 0x80)],
+                _ => Some(char::from(b)),
+            })
+            .collect(),
+        Encoding::Unknown => None,
+    }
+}
+
+fn encode(s: &str, encoding: Encoding) -> Option<Vec<u8>> {
+    match encoding {
+        Encoding::Utf8 => Some(s.as_bytes().to_vec()),
+        Encoding::Latin1 => s.chars().map(|c| u8::try_from(c).ok()).collect(),
+        Encoding::Cp1252 => s
+            .chars()
+            .map(|c| {
+                CP1252_HIGH
+                    .iter()
+                    .position(|&high| high == Some(c))
+                    .map(|i| 0x80 + i as u8)
+                    .or_else(|| u8::try_from(c).ok().filter(|b| !(0x80..=0x9F).contains(b)))
+            })
+            .collect(),
+        Encoding::Unknown => None,
+    }
+}
@@ -0,0 +1,59 @@
+use std::path::{Path, PathBuf};
+
+use crate::{compile, dashify_path, CompiledOptions, DashifyError, DashifyOptions};
+
+/// `dashify` for paths: only the file name is dashified, the parent directories are kept as they are.
+///
+/// ```no_run
+/// use std::fs;
+/// use std::path::Path;
+///
+/// use dashify::{DashifyExt, DashifyOptions};
+///
+/// let options = DashifyOptions::default();
+/// for entry in fs::read_dir("Downloads")? {
+///     let path = entry?.path();
+///     if !path.is_dashified(&options) {
+///         fs::rename(&path, path.dashify(&options)?)?;
+///     }
+/// }
+/// assert_eq!(Path::new("My Docs/Some File.txt").dashify(&options)?, Path::new("My Docs/some-file.txt"));
+/// # Ok::<(), Box<dyn std::error::Error>>(())
+/// ```
+pub trait DashifyExt {
+    /// The path with its file name dashified; fails with `NoFilename` for paths like `/` or `..`.
+    fn dashify(&self, options: &DashifyOptions) -> Result<PathBuf, DashifyError>;
+
+    /// Whether the file name would come out unchanged. Paths without a file name count as dashified.
+    fn is_dashified(&self, options: &DashifyOptions) -> bool;
+}
+
+impl DashifyExt for Path {
+    fn dashify(&self, options: &DashifyOptions) -> Result<PathBuf, DashifyError> {
+        dashify_path(self, options)
+    }
+
+    fn is_dashified(&self, options: &DashifyOptions) -> bool {
+        self.file_name().is_none() || compile(options).is_ok_and(|options| is_path_dashified(self, &options))
+    }
+}
+
+pub(crate) fn is_path_dashified(path: &Path, options: &CompiledOptions<'_>) -> bool {
+    let Some(file_name) = path.file_name() else {
+        return true;
+    };
+    match file_name.to_str() {
+        Some(file_name) => options.is_dashified(file_name),
+        None => options.dashify_os_str(file_name).is_ok_and(|dashified| dashified == file_name),
+    }
+}
+
+impl DashifyExt for PathBuf {
+    fn dashify(&self, options: &DashifyOptions) -> Result<PathBuf, DashifyError> {
+        self.as_path().dashify(options)
+    }
+
+    fn is_dashified(&self, options: &DashifyOptions) -> bool {
+        self.as_path().is_dashified(options)
+    }
+}
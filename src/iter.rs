@@ -0,0 +1,50 @@
+use std::borrow::Cow;
+use std::path::{Path, PathBuf};
+
+use walkdir::WalkDir;
+
+use crate::{is_resource_fork, CompiledOptions, DashifyOptions};
+
+/// Walks a directory, yielding each file's path along with its dashified name.
+pub struct DashifyIterator {
+    inner: walkdir::IntoIter,
+    skip_resource_forks: bool,
+    /// `None` when a `preserve_patterns` entry is invalid, which `dashify` fails on, so every name is kept.
+    options: Option<CompiledOptions<'static>>,
+}
+
+impl DashifyIterator {
+    pub fn new(root: &Path, options: DashifyOptions, recursive: bool) -> Self {
+        let walker = WalkDir::new(root).min_depth(1);
+        let walker = if recursive { walker } else { walker.max_depth(1) };
+        Self {
+            inner: walker.into_iter(),
+            skip_resource_forks: options.skip_resource_forks,
+            options: CompiledOptions::new(Cow::Owned(options)).ok(),
+        }
+    }
+}
+
+impl Iterator for DashifyIterator {
+    type Item = Result<(PathBuf, String), walkdir::Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let entry = match self.inner.next()? {
+                Ok(entry) => entry,
+                Err(e) => return Some(Err(e)),
+            };
+            if !entry.file_type().is_file() {
+                continue;
+            }
+            let file_name = entry.file_name().to_string_lossy();
+            if self.skip_resource_forks && is_resource_fork(&file_name) {
+                continue;
+            }
+            // a name dashify can't handle is kept as it is
+            let dashified = self.options.as_ref().and_then(|options| options.dashify(&file_name).ok());
+            let dashified = dashified.unwrap_or_else(|| file_name.to_string());
+            return Some(Ok((entry.into_path(), dashified)));
+        }
+    }
+}
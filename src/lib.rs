@@ -0,0 +1,1820 @@
+use filetime::FileTime;
+use rayon::prelude::*;
+#[cfg(feature = "regex-patterns")]
+use regex::Regex;
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Serialize};
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::ffi::{OsStr, OsString};
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::{Component, Path, PathBuf};
+use std::sync::Arc;
+use transform::collapse_mixed_separators;
+use unicode_normalization::UnicodeNormalization;
+
+#[cfg(feature = "async")]
+mod async_rename;
+mod audit;
+mod builder;
+mod compiled;
+mod completion;
+mod config;
+mod convention;
+mod encoding;
+mod ext;
+mod iter;
+mod manifest;
+mod pipeline;
+mod patterns;
+mod plan;
+mod preview;
+mod rename;
+mod report;
+mod script;
+mod session;
+mod sql;
+pub mod test_helpers;
+pub mod transform;
+#[cfg(feature = "watch")]
+mod watch;
+
+#[cfg(feature = "async")]
+pub use async_rename::dashify_dir_async;
+#[cfg(feature = "serde")]
+pub use audit::JsonLinesLogger;
+pub use audit::{LogEntry, LogOp, Logger, NoopLogger};
+pub use builder::DashifyOptionsBuilder;
+pub use compiled::CompiledOptions;
+pub use completion::{generate_completion_script, Shell};
+pub use config::{find_config_file, ConfigError, CONFIG_FILE_NAME};
+pub use convention::{convert_naming_convention, detect_naming_convention, NamingConvention};
+pub use encoding::{dashify_bytes, detect_encoding, Encoding};
+pub use ext::DashifyExt;
+pub use iter::DashifyIterator;
+pub use manifest::batch_rename_from_csv;
+pub use pipeline::DashifyPipeline;
+pub use plan::{compute_rename_plan, group_renames_by_directory, RenameConflict, RenamePlan};
+pub use preview::preview_rename_colored;
+pub use rename::{
+    apply_renames_parallel, copy_file_with_conflict_strategy, dashify_multiple_files_in_transaction,
+    rename_file_with_conflict_strategy, rename_in_place, safe_rename, CollisionStrategy,
+};
+pub use report::generate_html_report;
+pub use script::{generate_mv_script, parse_rename_script, ParseError};
+pub use session::{RenameSession, RollbackError};
+pub use sql::{dashify_sql_identifier, SqlDialect};
+#[cfg(feature = "watch")]
+pub use watch::watch_for_new_files;
+
+const DASH_CHARS: &[char] = &[
+    ' ', ',', '(', ')', '[', ']', '{', '}', '&', '+', '\'', '"', '!', '?', '#', '@', '$', '%', ';', ':', '=', '|', '*',
+    '<', '>', '\\', '^', '`',
+];
+
+const COMPOUND_EXTENSIONS: &[&str] = &["tar.gz", "tar.bz2", "tar.xz"];
+
+const WINDOWS_ILLEGAL_CHARS: &[char] = &['<', '>', ':', '"', '/', '\\', '|', '?', '*'];
+
+const WINDOWS_RESERVED_NAMES: &[&str] = &[
+    "con", "prn", "aux", "nul", "com1", "com2", "com3", "com4", "com5", "com6", "com7", "com8", "com9", "lpt1", "lpt2",
+    "lpt3", "lpt4", "lpt5", "lpt6", "lpt7", "lpt8", "lpt9",
+];
+
+/// What to do with `~` in names, e.g. Emacs backups (`file.txt~`).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(rename_all = "kebab-case"))]
+pub enum TildeHandling {
+    Preserve,
+    #[default]
+    Remove,
+    ToHyphen,
+}
+
+pub type FilterFn = Arc<dyn Fn(&str) -> bool + Send + Sync>;
+
+/// With the `serde` feature this (de)serializes under the field names below; missing fields keep their defaults,
+/// and `filter_fn` and `leave_alone_patterns` are skipped.
+#[derive(Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize), serde(default, deny_unknown_fields))]
+pub struct DashifyOptions {
+    /// Replace underscores with `separator` instead of keeping them as separators.
+    pub force_dash: bool,
+    /// Make `dashify_checked` fail when the filename is already dashified.
+    pub require_change: bool,
+    /// Map typographic quotes (`“ ” « » ‘ ’`) to their ASCII equivalents, which then become separators like any
+    /// other quote, so `“Draft”.txt` becomes `draft.txt` instead of being left alone as non-ASCII. On by default.
+    #[cfg_attr(feature = "serde", serde(alias = "handle_unicode_quotes"))]
+    pub normalize_quotes: bool,
+    /// Treat figure, en and em dashes (`‒`, `–`, `—`) as `-`, so `my—report.docx` becomes `my-report.docx`
+    /// instead of being left alone as non-ASCII. On by default.
+    pub normalize_dashes: bool,
+    /// Remove zero-width spaces, joiners and non-joiners and byte order marks, which are invisible but would
+    /// otherwise get the name left alone as non-ASCII. On by default.
+    pub strip_zero_width: bool,
+    /// Leave alone names whose stem entropy (see `measure_entropy`) exceeds this, treating them as opaque ids.
+    pub min_entropy: Option<f64>,
+    /// Restore the original access and modification times after renaming.
+    pub preserve_timestamps: bool,
+    /// Called with the filename before processing; returning `false` leaves the name unchanged.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub filter_fn: Option<FilterFn>,
+    /// Extra leave-alone rules, checked after the built-in ones against the full filename.
+    #[cfg(feature = "regex-patterns")]
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub leave_alone_patterns: Vec<Regex>,
+    /// Skip macOS resource fork files (`._name`) entirely when processing directories.
+    pub skip_resource_forks: bool,
+    /// Keep resource fork names as-is; when false only the part after `._` is dashified.
+    pub leave_resource_forks_alone: bool,
+    pub tilde_handling: TildeHandling,
+    /// Strip non-ASCII characters instead of leaving such names alone. Lossy: `naïve.txt` becomes `nave.txt`.
+    pub force_ascii: bool,
+    /// Let `from_project_dir` pick up `DASHIFY_*` settings from a `.env` file.
+    pub respect_dotenv: bool,
+    /// Remove download noise first, see `strip_common_noise_words`.
+    pub strip_noise_words: bool,
+    /// Count names left alone for being non-ASCII in `ProcessingStats::unicode_skipped`.
+    pub report_non_ascii_separately: bool,
+    /// Letters a word needs, counting a leading capital, before an uppercase letter starts a new word.
+    /// - 1 splits at every such boundary: `"iBurst"` -> `"i-burst"`, `"iOSDevice"` -> `"i-os-device"`.
+    /// - The default 2 keeps one-letter prefixes attached: `"iburst"`, `"ios-device"`, `"iphone"`, while
+    ///   `"myFile"` and `"ToDo"` still become `"my-file"` and `"to-do"`.
+    /// - 3 also joins two-letter words: `"myfile"`, `"todo"`. `"iOSDevice"` still becomes `"ios-device"`, not
+    ///   `"iosdevice"`, because the end of an acronym (`OS|Device`) is always a boundary, whatever this is set to.
+    pub abbreviation_min_length: usize,
+    /// Words with internal capitals that camelCase splitting should keep whole, such as `OAuth` or `GraphQL`, so
+    /// `GraphQLServer` becomes `graphql-server` rather than `graph-ql-server`. Matched ignoring case, but only
+    /// where a word can start and end, so `OAuth` is found in `MyOAuthClient` but not in `photoauthor`.
+    #[cfg_attr(feature = "serde", serde(alias = "acronym"))]
+    pub acronym_dictionary: Vec<String>,
+    /// Keep ordinals such as `1st`, `2nd`, `3rd` and `4th` whole instead of splitting the number from its
+    /// suffix, so `3rdParty` becomes `3rd-party` rather than `3-rd-party`. On by default.
+    pub preserve_ordinals: bool,
+    /// Keep hex literals such as `0xDEAD` whole instead of splitting digits from letters, so
+    /// `firmware_0x1A2B.hex` becomes `firmware_0x1a2b.hex` rather than `firmware_0-x-1-a-2-b.hex`. On by default.
+    /// A name that is only a hex literal, such as `0X1A2B.bin`, is lowercased rather than left alone as all-caps.
+    pub preserve_hex: bool,
+    /// Keep UUIDs, with or without dashes, whole instead of splitting digits from letters, so
+    /// `550E8400-E29B-41D4-A716-446655440000.JSON` is only lowercased. On by default. A bare uppercase UUID, such
+    /// as `550E8400E29B41D4A716446655440000.JSON`, is lowercased too rather than left alone as all-caps.
+    pub preserve_uuid: bool,
+    /// Keep dates such as `20240115`, `2024-01-15` and `2024Jan15` whole, so `meeting2024Jan15.docx` becomes
+    /// `meeting-2024jan15.docx` rather than `meeting-2024-jan-15.docx`. On by default.
+    pub preserve_dates: bool,
+    /// Keep only the first this many words of the stem, cutting at a separator so no word is split.
+    pub max_stem_words: Option<usize>,
+    /// Joins words, `-` by default. Any run of separators becomes this one character, except that a run of only
+    /// underscores is kept as `_` unless `force_dash` is set. With `'_'`, `"My File.txt"` becomes
+    /// `"my_file.txt"`; with `'.'`, `"my.file.txt"`.
+    pub separator: char,
+    /// Transliterate non-ASCII names to ASCII before processing instead of leaving them alone, e.g. `café.txt`
+    /// becomes `cafe.txt` and `Москва.txt` becomes `moskva.txt`.
+    pub transliterate: bool,
+    /// Remove accents and other combining diacritics, keeping the base letter, e.g. `naïve.txt` becomes
+    /// `naive.txt`. Lighter than `transliterate`: other scripts are untouched, so such names are still left alone.
+    pub strip_diacritics: bool,
+    /// Leave alone names matched in full by any of these regexes, e.g. `Makefile|Dockerfile` or `go\.(mod|sum)`.
+    /// `validate_options` reports invalid patterns, and `dashify` fails on them.
+    #[cfg(feature = "regex-patterns")]
+    #[cfg_attr(feature = "serde", serde(alias = "preserve"))]
+    pub preserve_patterns: Vec<String>,
+    /// Multi-part extensions kept whole, so `"Foo.d.ts"` keeps `d.ts` rather than treating `Foo.d` as the stem.
+    /// Defaults to `tar.gz`, `tar.bz2` and `tar.xz`; the longest match wins, see `is_compound_extension`.
+    #[cfg_attr(feature = "serde", serde(alias = "compound_ext"))]
+    pub compound_extensions: Vec<String>,
+    /// Shorten the stem to at most this many characters once everything else is done, cutting at the last
+    /// separator within the limit, or mid-word if there is none. The extension doesn't count.
+    pub max_length: Option<usize>,
+    /// Treat names as arbitrary text and produce URL slugs, see `dashify_slug`.
+    pub slug_mode: bool,
+}
+
+impl Default for DashifyOptions {
+    fn default() -> Self {
+        Self {
+            force_dash: false,
+            require_change: false,
+            normalize_quotes: true,
+            normalize_dashes: true,
+            strip_zero_width: true,
+            min_entropy: None,
+            preserve_timestamps: false,
+            filter_fn: None,
+            #[cfg(feature = "regex-patterns")]
+            leave_alone_patterns: Vec::new(),
+            skip_resource_forks: true,
+            leave_resource_forks_alone: true,
+            tilde_handling: TildeHandling::default(),
+            force_ascii: false,
+            respect_dotenv: true,
+            strip_noise_words: false,
+            report_non_ascii_separately: false,
+            abbreviation_min_length: 2,
+            acronym_dictionary: Vec::new(),
+            preserve_ordinals: true,
+            preserve_hex: true,
+            preserve_uuid: true,
+            preserve_dates: true,
+            max_stem_words: None,
+            separator: '-',
+            transliterate: false,
+            strip_diacritics: false,
+            #[cfg(feature = "regex-patterns")]
+            preserve_patterns: Vec::new(),
+            compound_extensions: COMPOUND_EXTENSIONS.iter().map(|extension| extension.to_string()).collect(),
+            max_length: None,
+            slug_mode: false,
+        }
+    }
+}
+
+impl DashifyOptions {
+    pub fn builder() -> DashifyOptionsBuilder {
+        DashifyOptionsBuilder::new()
+    }
+
+    /// Slug mode, as used by `dashify_slug`, for URL path segments.
+    pub fn for_url() -> Self {
+        Self::builder().slug_mode(true).separator('-').build().expect("preset options are valid")
+    }
+
+    /// Slug mode with names capped at 63 characters, for Kubernetes resource names; see `dashify_k8s`.
+    pub fn for_k8s() -> Self {
+        Self::builder().slug_mode(true).max_length(63).build().expect("preset options are valid")
+    }
+
+    /// Words joined by underscores, for Python module names: `"DataLoader.py"` becomes `"data_loader.py"`.
+    pub fn for_python() -> Self {
+        Self::builder().separator('_').build().expect("preset options are valid")
+    }
+}
+
+impl fmt::Debug for DashifyOptions {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let DashifyOptions {
+            force_dash,
+            require_change,
+            normalize_quotes,
+            normalize_dashes,
+            strip_zero_width,
+            min_entropy,
+            preserve_timestamps,
+            filter_fn,
+            #[cfg(feature = "regex-patterns")]
+            leave_alone_patterns,
+            skip_resource_forks,
+            leave_resource_forks_alone,
+            tilde_handling,
+            force_ascii,
+            respect_dotenv,
+            strip_noise_words,
+            report_non_ascii_separately,
+            abbreviation_min_length,
+            acronym_dictionary,
+            preserve_ordinals,
+            preserve_hex,
+            preserve_uuid,
+            preserve_dates,
+            max_stem_words,
+            separator,
+            transliterate,
+            strip_diacritics,
+            #[cfg(feature = "regex-patterns")]
+            preserve_patterns,
+            compound_extensions,
+            max_length,
+            slug_mode,
+        } = self;
+        let mut debug = f.debug_struct("DashifyOptions");
+        debug
+            .field("force_dash", force_dash)
+            .field("require_change", require_change)
+            .field("normalize_quotes", normalize_quotes)
+            .field("normalize_dashes", normalize_dashes)
+            .field("strip_zero_width", strip_zero_width)
+            .field("min_entropy", min_entropy)
+            .field("preserve_timestamps", preserve_timestamps)
+            .field("filter_fn", &filter_fn.as_ref().map(|_| "Fn(&str) -> bool"));
+        #[cfg(feature = "regex-patterns")]
+        debug.field("leave_alone_patterns", leave_alone_patterns);
+        debug
+            .field("skip_resource_forks", skip_resource_forks)
+            .field("leave_resource_forks_alone", leave_resource_forks_alone)
+            .field("tilde_handling", tilde_handling)
+            .field("force_ascii", force_ascii)
+            .field("respect_dotenv", respect_dotenv)
+            .field("strip_noise_words", strip_noise_words)
+            .field("report_non_ascii_separately", report_non_ascii_separately)
+            .field("abbreviation_min_length", abbreviation_min_length)
+            .field("acronym_dictionary", acronym_dictionary)
+            .field("preserve_ordinals", preserve_ordinals)
+            .field("preserve_hex", preserve_hex)
+            .field("preserve_uuid", preserve_uuid)
+            .field("preserve_dates", preserve_dates)
+            .field("max_stem_words", max_stem_words)
+            .field("separator", separator)
+            .field("transliterate", transliterate)
+            .field("strip_diacritics", strip_diacritics);
+        #[cfg(feature = "regex-patterns")]
+        debug.field("preserve_patterns", preserve_patterns);
+        debug
+            .field("compound_extensions", compound_extensions)
+            .field("max_length", max_length)
+            .field("slug_mode", slug_mode)
+            .finish()
+    }
+}
+
+#[derive(Debug)]
+pub enum DashifyError {
+    NoChangeRequired { filename: String },
+    CollisionDetected { first: String, second: String, target: String },
+    Io { path: PathBuf, source: io::Error },
+    NoFilename { path: PathBuf },
+    InvalidOptions { reason: String },
+    #[cfg(feature = "regex-patterns")]
+    InvalidPattern { pattern: String, source: regex::Error },
+    EmptyResult { input: String },
+    /// `error` made a multi-file rename fail, and some of the renames already done couldn't be undone either;
+    /// `stranded` is where those files were left.
+    IncompleteRollback { error: Box<DashifyError>, stranded: Vec<PathBuf> },
+}
+
+impl fmt::Display for DashifyError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DashifyError::NoChangeRequired { filename } => write!(f, "'{filename}' is already dashified"),
+            DashifyError::CollisionDetected { first, second, target } => {
+                write!(f, "'{first}' and '{second}' would both become '{target}'")
+            }
+            DashifyError::Io { path, source } => {
+                write!(f, "Could not rename '{}': {}", path.display(), describe_io_error(source))
+            }
+            DashifyError::NoFilename { path } => write!(f, "'{}' has no file name", path.display()),
+            DashifyError::InvalidOptions { reason } => write!(f, "invalid options: {reason}"),
+            #[cfg(feature = "regex-patterns")]
+            DashifyError::InvalidPattern { pattern, source } => write!(f, "invalid pattern '{pattern}': {source}"),
+            DashifyError::EmptyResult { input } => write!(f, "nothing usable is left of '{input}'"),
+            DashifyError::IncompleteRollback { error, stranded } => {
+                let stranded: Vec<_> = stranded.iter().map(|path| format!("'{}'", path.display())).collect();
+                write!(f, "{error}; could not undo the renames of {}", stranded.join(", "))
+            }
+        }
+    }
+}
+
+impl std::error::Error for DashifyError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            DashifyError::Io { source, .. } => Some(source),
+            DashifyError::IncompleteRollback { error, .. } => Some(error.as_ref()),
+            #[cfg(feature = "regex-patterns")]
+            DashifyError::InvalidPattern { source, .. } => Some(source),
+            _ => None,
+        }
+    }
+}
+
+// "Permission denied (os error 13)" -> "permission denied"
+fn describe_io_error(error: &io::Error) -> String {
+    let message = error.to_string();
+    let message = match message.rfind(" (os error ") {
+        Some(pos) => &message[..pos],
+        None => &message,
+    };
+    let mut chars = message.chars();
+    match chars.next() {
+        Some(first) => first.to_lowercase().chain(chars).collect(),
+        None => String::new(),
+    }
+}
+
+/// Dashifies `filename`. Names that are left alone or already clean come back unchanged.
+///
+/// Fails with `InvalidPattern` if one of `preserve_patterns` isn't a valid regex, and with `EmptyResult` if
+/// nothing would be left of the name, e.g. for `"---.txt"`. The patterns are compiled on every call; to dashify
+/// many names with them, use `CompiledOptions::dashify`.
+pub fn dashify(filename: &str, options: &DashifyOptions) -> Result<String, DashifyError> {
+    dashify_with_info(filename, options).map(|result| result.transformed)
+}
+
+/// A kind of change `dashify` can make to a name, as one bit of `DashifyResult::steps_applied`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+#[repr(u32)]
+pub enum TransformKind {
+    CamelCase = 1,
+    NumberSplit = 1 << 1,
+    SpecialChar = 1 << 2,
+    SeparatorCollapse = 1 << 3,
+    Lowercase = 1 << 4,
+}
+
+impl TransformKind {
+    pub const ALL: [TransformKind; 5] = [
+        TransformKind::CamelCase,
+        TransformKind::NumberSplit,
+        TransformKind::SpecialChar,
+        TransformKind::SeparatorCollapse,
+        TransformKind::Lowercase,
+    ];
+
+    pub fn bit(self) -> u32 {
+        self as u32
+    }
+
+    // the stages of `dashify_traced` that count as this kind of change; the rest, like truncation, don't
+    fn of_stage(stage: &str) -> Option<TransformKind> {
+        match stage {
+            "split camelCase" => Some(TransformKind::CamelCase),
+            "split numbers" => Some(TransformKind::NumberSplit),
+            "replace special characters" | "slugify" => Some(TransformKind::SpecialChar),
+            "collapse separators" | "trim separators" => Some(TransformKind::SeparatorCollapse),
+            "lowercase" | "lowercase extension" => Some(TransformKind::Lowercase),
+            _ => None,
+        }
+    }
+}
+
+/// What `dashify_with_info` made of a name.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct DashifyResult {
+    pub original: String,
+    pub transformed: String,
+    pub changed: bool,
+    /// The `TransformKind` bits of every change made; 0 when the name is unchanged.
+    pub steps_applied: u32,
+}
+
+impl DashifyResult {
+    pub fn applied(&self, kind: TransformKind) -> bool {
+        self.steps_applied & kind.bit() != 0
+    }
+
+    /// The kinds of change made, in pipeline order.
+    pub fn kinds(&self) -> impl Iterator<Item = TransformKind> + '_ {
+        TransformKind::ALL.into_iter().filter(|&kind| self.applied(kind))
+    }
+}
+
+impl fmt::Display for DashifyResult {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.changed {
+            write!(f, "'{}' -> '{}'", self.original, self.transformed)
+        } else {
+            write!(f, "'{}' is unchanged", self.original)
+        }
+    }
+}
+
+/// Like `dashify`, but also reports whether the name changed and which kinds of change were made.
+pub fn dashify_with_info(filename: &str, options: &DashifyOptions) -> Result<DashifyResult, DashifyError> {
+    dashify_compiled_with_info(filename, &compile(options)?)
+}
+
+pub(crate) fn dashify_compiled_with_info(
+    filename: &str,
+    options: &CompiledOptions<'_>,
+) -> Result<DashifyResult, DashifyError> {
+    let mut trace = Trace::Kinds(0);
+    let transformed = dashify_traced(filename, options, &mut trace)?;
+    let changed = transformed != filename;
+    let steps_applied = match trace {
+        // as with `explain`, stages may have run before dashify gave up and returned the original
+        Trace::Kinds(bits) if changed => bits,
+        _ => 0,
+    };
+    Ok(DashifyResult {
+        original: filename.to_string(),
+        transformed,
+        changed,
+        steps_applied,
+    })
+}
+
+/// Like `dashify`, but for filenames that need not be valid UTF-8, and it returns `filename` unchanged instead of
+/// failing; `dashify_path` reports the error instead.
+///
+/// On Unix, bytes that aren't valid UTF-8 are kept as they are and the text around them is dashified, with only
+/// the text after the last such byte treated as having an extension. Elsewhere the name is converted lossily.
+pub fn dashify_lossy(filename: &OsStr, options: &DashifyOptions) -> OsString {
+    dashify_os_str(filename, options).unwrap_or_else(|_| filename.to_os_string())
+}
+
+pub(crate) fn dashify_os_str(filename: &OsStr, options: &DashifyOptions) -> Result<OsString, DashifyError> {
+    compile(options)?.dashify_os_str(filename)
+}
+
+pub(crate) fn dashify_compiled_os_str(
+    filename: &OsStr,
+    options: &CompiledOptions<'_>,
+) -> Result<OsString, DashifyError> {
+    match filename.to_str() {
+        Some(filename) => options.dashify(filename).map(OsString::from),
+        None => Ok(dashify_non_utf8(filename, options)),
+    }
+}
+
+#[cfg(unix)]
+fn dashify_non_utf8(filename: &OsStr, options: &CompiledOptions<'_>) -> OsString {
+    use std::os::unix::ffi::{OsStrExt, OsStringExt};
+
+    if options.filter_fn.as_ref().is_some_and(|filter| !filter(&filename.to_string_lossy())) {
+        return filename.to_os_string();
+    }
+    let chunks: Vec<_> = filename.as_bytes().utf8_chunks().collect();
+    let mut dashified = Vec::with_capacity(filename.len());
+    for (i, chunk) in chunks.iter().enumerate() {
+        let is_last = i + 1 == chunks.len() && chunk.invalid().is_empty();
+        let (name, extension) = if is_last {
+            split_name_and_extension_with(chunk.valid(), &options.compound_extensions)
+        } else {
+            (chunk.valid(), None)
+        };
+        dashified.extend_from_slice(process_name(name, options).as_bytes());
+        if let Some(extension) = extension {
+            dashified.push(b'.');
+            dashified.extend_from_slice(extension.to_lowercase().as_bytes());
+        }
+        dashified.extend_from_slice(chunk.invalid());
+    }
+    OsString::from_vec(dashified)
+}
+
+// the lossy conversion can't be undone, so only a name that dashify changes is worth returning
+#[cfg(not(unix))]
+fn dashify_non_utf8(filename: &OsStr, options: &CompiledOptions<'_>) -> OsString {
+    let lossy = filename.to_string_lossy();
+    match options.dashify(&lossy) {
+        Ok(dashified) if dashified != lossy => dashified.into(),
+        _ => filename.to_os_string(),
+    }
+}
+
+/// One stage of `dashify` that changed the name, as reported by `explain`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TransformStep {
+    pub name: &'static str,
+    pub before: String,
+    pub after: String,
+}
+
+/// Runs `dashify` and returns the stages that changed something, in order. The splitting and cleanup
+/// stages see only the stem, without the extension.
+///
+/// An empty result means the name comes out unchanged, e.g. because it is already clean or left alone.
+pub fn explain(filename: &str, options: &DashifyOptions) -> Vec<TransformStep> {
+    let Ok(options) = compile(options) else {
+        return Vec::new();
+    };
+    let mut trace = Trace::On(Vec::new());
+    let dashified = dashify_traced(filename, &options, &mut trace);
+    match trace {
+        // the stages may have run before dashify gave up and returned the original or failed
+        Trace::On(steps) if dashified.is_ok_and(|dashified| dashified != filename) => steps,
+        _ => Vec::new(),
+    }
+}
+
+/// `explain` formatted as a table, one stage per line.
+pub fn explain_to_string(filename: &str, options: &DashifyOptions) -> String {
+    let steps = explain(filename, options);
+    if steps.is_empty() {
+        return format!("'{filename}' is unchanged\n");
+    }
+    let width = steps.iter().map(|step| step.name.len()).max().unwrap_or(0);
+    let before_width = steps.iter().map(|step| step.before.chars().count()).max().unwrap_or(0);
+    steps
+        .iter()
+        .map(|step| format!("{:width$}  {:before_width$}  ->  {}\n", step.name, step.before, step.after))
+        .collect()
+}
+
+enum Trace {
+    Off,
+    /// Only the `TransformKind` bits of the stages that changed something.
+    Kinds(u32),
+    On(Vec<TransformStep>),
+}
+
+impl Trace {
+    fn record(&mut self, name: &'static str, before: &str, after: &str) {
+        match self {
+            Trace::Off => {}
+            _ if before == after => {}
+            Trace::Kinds(bits) => *bits |= TransformKind::of_stage(name).map_or(0, TransformKind::bit),
+            Trace::On(steps) => steps.push(TransformStep {
+                name,
+                before: before.to_string(),
+                after: after.to_string(),
+            }),
+        }
+    }
+}
+
+/// `dashify` for a name decoded from a legacy encoding such as Latin-1, in which accented letters are ordinary
+/// letters, so the name isn't left alone for not being ASCII.
+pub(crate) fn dashify_decoded(filename: &str, options: &DashifyOptions) -> Result<String, DashifyError> {
+    dashify_traced_with(filename, &compile(options)?, &mut Trace::Off, matches_leave_alone_rule)
+}
+
+// `options` as the pipeline takes them, with `preserve_patterns` compiled for this one call
+pub(crate) fn compile(options: &DashifyOptions) -> Result<CompiledOptions<'_>, DashifyError> {
+    CompiledOptions::new(Cow::Borrowed(options))
+}
+
+fn dashify_traced(filename: &str, options: &CompiledOptions<'_>, trace: &mut Trace) -> Result<String, DashifyError> {
+    dashify_traced_with(filename, options, trace, should_leave_alone)
+}
+
+fn dashify_traced_with(
+    filename: &str,
+    options: &CompiledOptions<'_>,
+    trace: &mut Trace,
+    leave_alone: fn(&str, &CompiledOptions<'_>) -> bool,
+) -> Result<String, DashifyError> {
+    if options.filter_fn.as_ref().is_some_and(|filter| !filter(filename)) {
+        return Ok(filename.to_string());
+    }
+    if !options.slug_mode && !options.leave_resource_forks_alone && is_resource_fork(filename) {
+        return Ok(format!("._{}", dashify_traced_with(&filename[2..], options, trace, leave_alone)?));
+    }
+    let mut normalized = pre_normalize(filename, options);
+    if !options.slug_mode && leave_alone(&normalized, options) {
+        return Ok(filename.to_string());
+    }
+    trace.record("normalize unicode", filename, &normalized);
+    if options.strip_noise_words {
+        let stripped = strip_common_noise_words(&normalized);
+        trace.record("strip noise words", &normalized, &stripped);
+        normalized = Cow::Owned(stripped);
+    }
+    let clean = !options.slug_mode && is_already_clean(&normalized, options);
+    if clean && options.max_stem_words.is_none() && options.max_length.is_none() {
+        return Ok(normalized.into_owned());
+    }
+    let (name, extension) = if options.slug_mode {
+        (normalized.as_ref(), None)
+    } else {
+        split_name_and_extension_with(&normalized, &options.compound_extensions)
+    };
+    let mut name = if clean { name.to_string() } else { process_name_traced(name, options, trace) };
+    if options.slug_mode {
+        let slug = slugify(&name, options.separator);
+        trace.record("slugify", &name, &slug);
+        name = slug;
+    }
+    if let Some(max_words) = options.max_stem_words {
+        let truncated = truncate_words(&name, max_words, options.separator).to_string();
+        trace.record("limit words", &name, &truncated);
+        name = truncated;
+    }
+    if let Some(max_length) = options.max_length {
+        let truncated = truncate_length(&name, max_length, options.separator).to_string();
+        trace.record("limit length", &name, &truncated);
+        name = truncated;
+    }
+    if name.is_empty() {
+        return Err(DashifyError::EmptyResult {
+            input: filename.to_string(),
+        });
+    }
+    match extension {
+        Some(extension) => {
+            let lowercase = extension.to_lowercase();
+            trace.record("lowercase extension", extension, &lowercase);
+            Ok(format!("{name}.{lowercase}"))
+        }
+        None => Ok(name),
+    }
+}
+
+/// Converts any string, not just a filename, to a URL slug of lowercase letters and digits joined by single
+/// dashes: `"Hello, World! (2024)"` becomes `"hello-world-2024"`. The whole string is processed, with none of
+/// the leave-alone exceptions and no extension; other characters are dropped, so non-ASCII text only survives
+/// with `transliterate` or `strip_diacritics`. Fails with `EmptyResult` if nothing is left.
+pub fn dashify_slug(s: &str, options: &DashifyOptions) -> Result<String, DashifyError> {
+    let options = DashifyOptions {
+        slug_mode: true,
+        separator: '-',
+        ..options.clone()
+    };
+    dashify(s, &options)
+}
+
+/// Converts `s` to a valid Kubernetes resource name: lowercase letters, digits and dashes, starting and ending
+/// with a letter or digit, at most 63 characters, cut at a dash where possible. Uses `DashifyOptions::for_k8s`.
+pub fn dashify_k8s(s: &str) -> Result<String, DashifyError> {
+    dashify(s, &DashifyOptions::for_k8s())
+}
+
+// "hello_world.v2-é" -> "hello-world-v2": separators and dots become dashes and anything else outside [a-z0-9]
+// is dropped
+fn slugify(s: &str, separator: char) -> String {
+    let slug: String = s
+        .chars()
+        .filter_map(|c| match c {
+            'a'..='z' | '0'..='9' | '-' => Some(c),
+            _ if c == '_' || c == '.' || c == separator => Some('-'),
+            _ => None,
+        })
+        .collect();
+    collapse_mixed_separators(&slug).trim_matches('-').to_string()
+}
+
+/// Dashifies every name in `filenames`, returning the results in order along with whether each one changed.
+/// Names that `dashify` fails on are returned unchanged.
+pub fn dashify_batch(filenames: &[&str], options: &DashifyOptions) -> (Vec<String>, Vec<bool>) {
+    let options = compile(options);
+    let mut dashified = Vec::with_capacity(filenames.len());
+    let mut changed = Vec::with_capacity(filenames.len());
+    for filename in filenames {
+        let name = dashify_or_keep(filename, &options);
+        changed.push(name != *filename);
+        dashified.push(name);
+    }
+    (dashified, changed)
+}
+
+/// Like `dashify_batch`, but spread over rayon's global thread pool. The results are still in input order.
+pub fn dashify_batch_parallel(filenames: &[&str], options: &DashifyOptions) -> (Vec<String>, Vec<bool>) {
+    let options = compile(options);
+    filenames
+        .par_iter()
+        .map(|filename| {
+            let name = dashify_or_keep(filename, &options);
+            let changed = name != *filename;
+            (name, changed)
+        })
+        .unzip()
+}
+
+fn dashify_or_keep(filename: &str, options: &Result<CompiledOptions<'_>, DashifyError>) -> String {
+    match options {
+        Ok(options) => options.dashify(filename).unwrap_or_else(|_| filename.to_string()),
+        Err(_) => filename.to_string(),
+    }
+}
+
+/// Dashifies the last component of `path`, keeping its parent directory as-is. A name that isn't valid UTF-8 is
+/// handled as by `dashify_lossy`.
+pub fn dashify_path(path: &Path, options: &DashifyOptions) -> Result<PathBuf, DashifyError> {
+    compile(options)?.dashify_path(path)
+}
+
+/// Dashifies every component of `path` except `.`, `..`, the root and any Windows prefix, so
+/// `My Documents/Work Files/Report.docx` becomes `my-documents/work-files/report.docx`.
+///
+/// Only the last component is treated as a file name with an extension; the others are directory names, see
+/// `dashify_dir_name`. A component that `dashify` fails on is kept as it is.
+pub fn normalize_path_components(path: &Path, options: &DashifyOptions) -> PathBuf {
+    let Ok(options) = compile(options) else {
+        return path.to_path_buf();
+    };
+    let mut components = path.components().peekable();
+    let mut normalized = PathBuf::new();
+    while let Some(component) = components.next() {
+        match component {
+            Component::Normal(name) if components.peek().is_none() => {
+                normalized.push(options.dashify_os_str(name).unwrap_or_else(|_| name.to_os_string()))
+            }
+            Component::Normal(name) => normalized.push(options.dashify_dir_name(name)),
+            other => normalized.push(other),
+        }
+    }
+    normalized
+}
+
+/// Dashifies a directory name. Unlike `dashify` nothing is split off as an extension, so `"Trip.2024 Photos"`
+/// becomes `"trip.2024-photos"`. Names that would be left alone, that nothing would be left of, or that aren't
+/// valid UTF-8 are returned unchanged.
+pub fn dashify_dir_name(name: &OsStr, options: &DashifyOptions) -> OsString {
+    match compile(options) {
+        Ok(options) => options.dashify_dir_name(name),
+        Err(_) => name.to_os_string(),
+    }
+}
+
+pub(crate) fn dashify_compiled_dir_name(name: &OsStr, options: &CompiledOptions<'_>) -> OsString {
+    let Some(name_str) = name.to_str() else {
+        return name.to_os_string();
+    };
+    if options.filter_fn.as_ref().is_some_and(|filter| !filter(name_str)) {
+        return name.to_os_string();
+    }
+    let normalized = pre_normalize(name_str, options);
+    if should_leave_alone(&normalized, options) {
+        return name.to_os_string();
+    }
+    if is_already_clean(&normalized, options) {
+        return normalized.as_ref().into();
+    }
+    let dashified = process_name(&normalized, options);
+    if dashified.is_empty() {
+        return name.to_os_string();
+    }
+    dashified.into()
+}
+
+/// Removes download and copy artifacts such as `Copy of`, `- Copy`, `(2)`, `[1]`, `final` and `v2` from the
+/// stem. Words are whitespace-separated; the name is returned unchanged if nothing would be left.
+pub fn strip_common_noise_words(name: &str) -> String {
+    let (stem, extension) = split_name_and_extension(name);
+    let stem = patterns::strip_copy_suffix(patterns::strip_copy_of_prefix(stem));
+    let words: Vec<&str> = stem.split_whitespace().filter(|word| !patterns::is_noise_word(word)).collect();
+    if words.is_empty() {
+        return name.to_string();
+    }
+    match extension {
+        Some(extension) => format!("{}.{extension}", words.join(" ")),
+        None => words.join(" "),
+    }
+}
+
+/// Dashifies `stem`, then restores the capitalization of any word from `reference` found in the result, e.g.
+/// a brand name: with reference `"MyBrand"`, `"MyBrand Logo.png"` becomes `"MyBrand-logo.png"`.
+pub fn dashify_preserving_case_of(stem: &str, reference: &str, options: &DashifyOptions) -> String {
+    let mut result = dashify(stem, options).unwrap_or_else(|_| stem.to_string());
+    for word in reference.split(|c: char| c.is_whitespace() || c == '-' || c == '_' || c == ',') {
+        if word.is_empty() {
+            continue;
+        }
+        let mut forms = vec![word.to_lowercase()];
+        let dashified = process_name(word, options);
+        if !dashified.is_empty() && dashified != forms[0] {
+            forms.push(dashified);
+        }
+        result = replace_whole_words(&result, &forms, word);
+    }
+    result
+}
+
+// Replaces each of `forms` in `s` with `word` where it stands between separators or dots, or at either end.
+fn replace_whole_words(s: &str, forms: &[String], word: &str) -> String {
+    let is_separator = |c: char| matches!(c, '-' | '_' | '.');
+    let mut result = String::with_capacity(s.len());
+    let mut rest = s;
+    while let Some(c) = rest.chars().next() {
+        if result.is_empty() || result.ends_with(is_separator) {
+            let whole = forms.iter().find(|form| {
+                rest.strip_prefix(form.as_str())
+                    .is_some_and(|after| after.chars().next().is_none_or(is_separator))
+            });
+            if let Some(form) = whole {
+                result.push_str(word);
+                rest = &rest[form.len()..];
+                continue;
+            }
+        }
+        result.push(c);
+        rest = &rest[c.len_utf8()..];
+    }
+    result
+}
+
+/// Dashifies `filename` and makes sure the result is also a valid Windows filename: characters Windows rejects
+/// become dashes, trailing dots and spaces are dropped, and reserved device names get `_win` appended to the
+/// stem (`"con.txt"` becomes `"con_win.txt"`).
+pub fn normalize_windows_filename(filename: &str) -> String {
+    // dashify leaves some names alone (non-ASCII, ALL_CAPS), so those can still contain illegal characters
+    let name: String = dashify(filename, &DashifyOptions::default())
+        .unwrap_or_else(|_| filename.to_string())
+        .chars()
+        .map(|c| if WINDOWS_ILLEGAL_CHARS.contains(&c) || c.is_control() { '-' } else { c })
+        .collect();
+    let name = collapse_mixed_separators(&name);
+    let name = name.trim_end_matches(['.', ' ']);
+    let (stem, rest) = name.split_at(name.find('.').unwrap_or(name.len()));
+    if stem.is_empty() && rest.is_empty() {
+        return "_win".to_string();
+    }
+    if WINDOWS_RESERVED_NAMES.contains(&stem.to_lowercase().as_str()) {
+        return format!("{stem}_win{rest}");
+    }
+    name.to_string()
+}
+
+/// The minimal cleanup for names that are already word-split: collapses redundant separators and dots and
+/// lowercases, without camelCase or number splitting.
+pub fn dashify_normalize_only(filename: &str) -> String {
+    let (name, extension) = split_name_and_extension(filename);
+    let name = collapse_separators(&name.to_lowercase());
+    match extension {
+        Some(extension) => format!("{name}.{}", extension.to_lowercase()),
+        None => name,
+    }
+}
+
+/// Checks that `options` make sense together, e.g. that `transliterate` and `strip_diacritics` aren't both set,
+/// and that every `preserve_patterns` entry is a valid regex, returning them compiled.
+pub fn validate_options(options: &DashifyOptions) -> Result<CompiledOptions<'_>, DashifyError> {
+    if options.transliterate && options.strip_diacritics {
+        return Err(DashifyError::InvalidOptions {
+            reason: "transliterate and strip_diacritics can't both be set".to_string(),
+        });
+    }
+    compile(options)
+}
+
+/// Like `dashify`, but honors `require_change` by failing on names that are already dashified.
+pub fn dashify_checked(filename: &str, options: &DashifyOptions) -> Result<String, DashifyError> {
+    let dashified = dashify(filename, options)?;
+    if options.require_change && dashified == filename {
+        return Err(DashifyError::NoChangeRequired {
+            filename: filename.to_string(),
+        });
+    }
+    Ok(dashified)
+}
+
+/// Converts `name` to an `UPPER_SNAKE_CASE` environment variable name; valid names are returned as-is. Anything
+/// but ASCII letters and digits becomes `_`, so `"a/b"` gives `"A_B"` and `"café"` gives `"CAF"`.
+pub fn dashify_env_var(name: &str, options: &DashifyOptions) -> String {
+    if patterns::is_env_var(name) {
+        return name.to_string();
+    }
+    let options = DashifyOptions {
+        force_dash: true,
+        separator: '-',
+        ..options.clone()
+    };
+    let key: String = process_name(name, &options)
+        .to_uppercase()
+        .chars()
+        .map(|c| if c.is_ascii_uppercase() || c.is_ascii_digit() { c } else { '_' })
+        .collect();
+    let mut key = patterns::collapse_runs(&key, '_').trim_matches('_').to_string();
+    if key.is_empty() || key.starts_with(|c: char| c.is_ascii_digit()) {
+        key.insert(0, '_');
+    }
+    key
+}
+
+/// Converts `name` to a Cargo feature name: lowercase words joined by dashes, prefixed with `f` if it would
+/// start with a digit. `"MyFeatureFlag"` becomes `"my-feature-flag"`, `"2FASupport"` becomes `"f-2-fa-support"`.
+pub fn dashify_cargo_feature(name: &str) -> String {
+    let options = DashifyOptions {
+        force_dash: true,
+        ..DashifyOptions::default()
+    };
+    let name = process_name(name, &options);
+    let name: String = name
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '-' })
+        .collect();
+    let feature = collapse_mixed_separators(&name).trim_matches('-').to_string();
+    if feature.starts_with(|c: char| c.is_ascii_digit()) {
+        format!("f-{feature}")
+    } else {
+        feature
+    }
+}
+
+/// Converts `name` to an HTTP header name in Title-Kebab-Case: `"x_request_id"` becomes `"X-Request-Id"`,
+/// `"contentType"` becomes `"Content-Type"`.
+pub fn dashify_header_name(name: &str) -> String {
+    let options = DashifyOptions {
+        force_dash: true,
+        ..DashifyOptions::default()
+    };
+    let name: String = process_name(name, &options)
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '-' })
+        .collect();
+    name.split('-')
+        .filter(|word| !word.is_empty())
+        .map(convention::capitalize)
+        .collect::<Vec<_>>()
+        .join("-")
+}
+
+/// Applies `dashify_env_var` to every key, failing if two different keys map to the same name.
+pub fn dashify_environment(
+    vars: impl IntoIterator<Item = (String, String)>,
+    options: &DashifyOptions,
+) -> Result<Vec<(String, String)>, DashifyError> {
+    let mut seen: HashMap<String, String> = HashMap::new();
+    let mut result = Vec::new();
+    for (key, value) in vars {
+        let new_key = dashify_env_var(&key, options);
+        if let Some(previous) = seen.get(&new_key) {
+            if *previous != key {
+                return Err(DashifyError::CollisionDetected {
+                    first: previous.clone(),
+                    second: key,
+                    target: new_key,
+                });
+            }
+        }
+        seen.insert(new_key.clone(), key);
+        result.push((new_key, value));
+    }
+    Ok(result)
+}
+
+/// Whether `dashify` would return `filename` unchanged. Names that are left alone or already clean are
+/// recognized without running the transformation pipeline.
+pub fn is_dashified(filename: &str, options: &DashifyOptions) -> bool {
+    compile(options).is_ok_and(|options| options.is_dashified(filename))
+}
+
+pub(crate) fn is_compiled_dashified(filename: &str, options: &CompiledOptions<'_>) -> bool {
+    if options.filter_fn.as_ref().is_some_and(|filter| !filter(filename)) {
+        return true;
+    }
+    let renames_fork = !options.leave_resource_forks_alone && is_resource_fork(filename);
+    // for ASCII names pre_normalize is a no-op, so these checks see exactly what dashify would
+    if filename.is_ascii() && !renames_fork && !options.slug_mode {
+        if should_leave_alone(filename, options) {
+            return true;
+        }
+        let may_shorten = options.strip_noise_words || options.max_stem_words.is_some() || options.max_length.is_some();
+        if !may_shorten && is_already_clean(filename, options) {
+            return true;
+        }
+    }
+    options.dashify(filename).is_ok_and(|dashified| dashified == filename)
+}
+
+/// Returns every file under `dir` whose name `dashify` would change, without renaming anything.
+pub fn find_problematic_filenames(dir: &Path, recursive: bool) -> io::Result<Vec<PathBuf>> {
+    Ok(compute_rename_plan(dir, recursive, &DashifyOptions::default())?.needs_renaming())
+}
+
+fn pre_normalize<'a>(filename: &'a str, options: &DashifyOptions) -> Cow<'a, str> {
+    if filename.is_ascii() {
+        return Cow::Borrowed(filename);
+    }
+    let mut normalized = Cow::Borrowed(filename);
+    if options.normalize_quotes || options.normalize_dashes || options.strip_zero_width {
+        normalized = Cow::Owned(
+            filename
+                .chars()
+                .filter(|&c| !(options.strip_zero_width && is_zero_width(c)))
+                .map(|c| ascii_punctuation(c, options.normalize_quotes, options.normalize_dashes).unwrap_or(c))
+                .collect(),
+        );
+    }
+    if options.transliterate && !normalized.is_ascii() {
+        normalized = Cow::Owned(unidecode::unidecode(&normalized));
+    }
+    if options.strip_diacritics && !normalized.is_ascii() {
+        normalized = Cow::Owned(strip_diacritics(&normalized));
+    }
+    if options.force_ascii && !normalized.is_ascii() {
+        if let Some(stripped) = strip_non_ascii(&normalized) {
+            normalized = Cow::Owned(stripped);
+        }
+    }
+    normalized
+}
+
+/// Replaces typographic quotes and dashes with their ASCII equivalents in one pass: `“Q1–Q2” report` becomes
+/// `"Q1-Q2" report`. This is what `dashify` does first with `normalize_quotes` and `normalize_dashes` set.
+pub fn normalize_unicode_punctuation(s: &str) -> String {
+    s.chars().map(|c| ascii_punctuation(c, true, true).unwrap_or(c)).collect()
+}
+
+// zero-width space, non-joiner and joiner, and the byte order mark
+fn is_zero_width(c: char) -> bool {
+    matches!(c, '\u{200B}' | '\u{200C}' | '\u{200D}' | '\u{FEFF}')
+}
+
+fn ascii_punctuation(c: char, quotes: bool, dashes: bool) -> Option<char> {
+    match c {
+        '\u{201C}' | '\u{201D}' | '\u{00AB}' | '\u{00BB}' if quotes => Some('"'),
+        '\u{2018}' | '\u{2019}' if quotes => Some('\''),
+        // figure, en and em dash
+        '\u{2012}' | '\u{2013}' | '\u{2014}' if dashes => Some('-'),
+        _ => None,
+    }
+}
+
+// "naïve" -> "naive": decompose, drop the combining diacritical marks, recompose what's left
+fn strip_diacritics(s: &str) -> String {
+    let is_diacritic = |c: &char| {
+        matches!(
+            *c,
+            '\u{0300}'..='\u{036F}'
+                | '\u{1AB0}'..='\u{1AFF}'
+                | '\u{1DC0}'..='\u{1DFF}'
+                | '\u{20D0}'..='\u{20FF}'
+                | '\u{FE20}'..='\u{FE2F}'
+        )
+    };
+    s.nfd().filter(|c| !is_diacritic(c)).nfc().collect()
+}
+
+// Lossy: drops every non-ASCII character. Gives up (None) if nothing usable would be left of the stem.
+fn strip_non_ascii(filename: &str) -> Option<String> {
+    let (name, extension) = split_name_and_extension(filename);
+    let name: String = name.chars().filter(char::is_ascii).collect();
+    if !name.chars().any(|c| c.is_ascii_alphanumeric()) {
+        return None;
+    }
+    match extension {
+        Some(extension) => Some(format!("{name}.{}", extension.chars().filter(char::is_ascii).collect::<String>())),
+        None => Some(name),
+    }
+}
+
+/// How alike two filenames are, from 0.0 to 1.0, ignoring case: one minus the edit distance over the longer
+/// length. A name and its dashified form score high, e.g. `"MyFile.txt"` and `"my-file.txt"` give about 0.91.
+pub fn similarity_score(a: &str, b: &str) -> f64 {
+    let a: Vec<char> = a.to_lowercase().chars().collect();
+    let b: Vec<char> = b.to_lowercase().chars().collect();
+    let longest = a.len().max(b.len());
+    if longest == 0 {
+        return 1.0;
+    }
+    1.0 - levenshtein(&a, &b) as f64 / longest as f64
+}
+
+fn levenshtein(a: &[char], b: &[char]) -> usize {
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, ca) in a.iter().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(ca != cb);
+            current.push(substitution.min(previous[j + 1] + 1).min(current[j] + 1));
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
+
+/// Shannon entropy, in bits per character, of the character distribution in the filename's stem.
+pub fn measure_entropy(filename: &str) -> f64 {
+    let (name, _) = split_name_and_extension(filename);
+    let mut counts: HashMap<char, usize> = HashMap::new();
+    for c in name.chars() {
+        *counts.entry(c).or_default() += 1;
+    }
+    let total = name.chars().count() as f64;
+    counts.values().fold(0.0, |entropy, &count| {
+        let p = count as f64 / total;
+        entropy + p * (1.0 / p).log2()
+    })
+}
+
+/// Whether `dashify` would leave `filename` alone specifically because it is not ASCII.
+pub(crate) fn is_skipped_as_non_ascii(filename: &str, options: &DashifyOptions) -> bool {
+    if options.slug_mode || options.filter_fn.as_ref().is_some_and(|filter| !filter(filename)) {
+        return false;
+    }
+    !pre_normalize(filename, options).is_ascii()
+}
+
+fn should_leave_alone(filename: &str, options: &CompiledOptions<'_>) -> bool {
+    !filename.is_ascii() || matches_leave_alone_rule(filename, options)
+}
+
+// every reason to leave a name alone except its not being ASCII
+fn matches_leave_alone_rule(filename: &str, options: &CompiledOptions<'_>) -> bool {
+    if filename.is_empty() || filename == "." || filename == ".." {
+        return true;
+    }
+    if options.leave_resource_forks_alone && is_resource_fork(filename) {
+        return true;
+    }
+    let (name, _) = split_name_and_extension_with(filename, &options.compound_extensions);
+    if is_dunder(name) || (is_all_caps_filename(name) && !is_literal(name, options)) || is_semver_style(filename) {
+        return true;
+    }
+    if options.min_entropy.is_some_and(|threshold| measure_entropy(filename) > threshold) {
+        return true;
+    }
+    matches_user_pattern(filename, options)
+}
+
+// `preserve_patterns` and `leave_alone_patterns`, which come with the `regex-patterns` feature
+#[cfg(feature = "regex-patterns")]
+fn matches_user_pattern(filename: &str, options: &CompiledOptions<'_>) -> bool {
+    options.matches_preserve_pattern(filename)
+        || options.leave_alone_patterns.iter().any(|pattern| pattern.is_match(filename))
+}
+
+#[cfg(not(feature = "regex-patterns"))]
+fn matches_user_pattern(_filename: &str, _options: &CompiledOptions<'_>) -> bool {
+    false
+}
+
+/// macOS AppleDouble files, e.g. `._My File.txt`, created alongside files copied to non-HFS+ volumes.
+pub fn is_resource_fork(filename: &str) -> bool {
+    filename.len() > 2 && filename.starts_with("._")
+}
+
+fn is_dunder(name: &str) -> bool {
+    name.len() > 4 && name.starts_with("__") && name.ends_with("__")
+}
+
+// Single-token names like README or CHANGELOG; MY_CONSTANT is several words and gets dashified
+fn is_all_caps_filename(name: &str) -> bool {
+    name.chars().any(|c| c.is_ascii_uppercase()) && name.chars().all(|c| c.is_ascii_uppercase() || c.is_ascii_digit())
+}
+
+// A stem that is one hex literal or UUID, like 0X1A2B, is a value to lowercase rather than an all-caps word like
+// README
+fn is_literal(name: &str, options: &DashifyOptions) -> bool {
+    let chars: Vec<char> = name.chars().collect();
+    (options.preserve_hex && !chars.is_empty() && detect_hex_prefix(&chars, 0) == Some(chars.len()))
+        || (options.preserve_uuid && looks_like_uuid(name))
+}
+
+fn is_semver_style(filename: &str) -> bool {
+    patterns::has_semver_prefix(filename)
+}
+
+fn is_already_clean(filename: &str, options: &DashifyOptions) -> bool {
+    let body = filename.strip_prefix('.').unwrap_or(filename);
+    if options.force_dash && body.contains('_') {
+        return false;
+    }
+    if options.separator != '-' && body.contains('-') {
+        return false;
+    }
+    let separator = options.separator;
+    let is_separator = |c: char| c == '-' || c == '_' || c == separator;
+    let is_punctuation = |c: char| is_separator(c) || c == '.';
+    let chars: Vec<char> = body.chars().collect();
+    chars
+        .iter()
+        .all(|&c| c.is_ascii_lowercase() || c.is_ascii_digit() || is_punctuation(c))
+        && !chars.first().is_some_and(|&c| is_separator(c))
+        && !chars.last().is_some_and(|&c| is_punctuation(c))
+        // no `--`, `_-`, `..`, `-.`, `._` and so on
+        && !chars.windows(2).any(|pair| is_punctuation(pair[0]) && is_punctuation(pair[1]))
+}
+
+fn split_name_and_extension(filename: &str) -> (&str, Option<&str>) {
+    split_name_and_extension_with(filename, COMPOUND_EXTENSIONS)
+}
+
+// Like `split_name_and_extension`, but with `compound_extensions` in place of the built-in ones.
+fn split_name_and_extension_with<'a>(
+    filename: &'a str,
+    compound_extensions: &[impl AsRef<str>],
+) -> (&'a str, Option<&'a str>) {
+    if let Some((name, extension)) = is_compound_extension(filename, compound_extensions) {
+        return (name, Some(extension));
+    }
+    let start = usize::from(filename.starts_with('.'));
+    match filename[start..].rfind('.') {
+        Some(pos) => {
+            let dot = start + pos;
+            let extension = &filename[dot + 1..];
+            if dot > start && !extension.is_empty() && extension.chars().all(|c| c.is_ascii_alphanumeric()) {
+                (&filename[..dot], Some(extension))
+            } else {
+                (filename, None)
+            }
+        }
+        None => (filename, None),
+    }
+}
+
+/// Splits off the longest of `extensions` that `filename` ends with, ignoring ASCII case, as `(stem, extension)`:
+/// `"Types.d.ts"` with `["ts", "d.ts"]` gives `("Types", "d.ts")`. Extensions may be given with or without a
+/// leading dot. Returns `None` if none match or nothing would be left of the stem.
+pub fn is_compound_extension<'a>(filename: &'a str, extensions: &[impl AsRef<str>]) -> Option<(&'a str, &'a str)> {
+    let start = usize::from(filename.starts_with('.'));
+    extensions
+        .iter()
+        .map(|extension| extension.as_ref().trim_start_matches('.'))
+        .filter(|extension| !extension.is_empty() && filename.len() > extension.len() + 1 + start)
+        .filter_map(|extension| {
+            let dot = filename.len() - extension.len() - 1;
+            let matches = filename.is_char_boundary(dot)
+                && filename[dot..].starts_with('.')
+                && filename[dot + 1..].eq_ignore_ascii_case(extension);
+            matches.then(|| (&filename[..dot], &filename[dot + 1..]))
+        })
+        .max_by_key(|(_, extension)| extension.len())
+}
+
+/// Splits at the first dot instead of the last: `"index.test.tsx"` gives `("index", ["test", "tsx"])`.
+///
+/// The leading dot of a hidden file belongs to the stem, so `".eslintrc.json"` gives `(".eslintrc", ["json"])`.
+pub fn split_name_and_all_extensions(filename: &str) -> (String, Vec<String>) {
+    let start = usize::from(filename.starts_with('.'));
+    match filename[start..].find('.') {
+        Some(pos) if pos > 0 => {
+            let dot = start + pos;
+            let extensions = filename[dot + 1..]
+                .split('.')
+                .filter(|part| !part.is_empty())
+                .map(str::to_string)
+                .collect();
+            (filename[..dot].to_string(), extensions)
+        }
+        _ => (filename.to_string(), Vec::new()),
+    }
+}
+
+type Stage = fn(&str, &DashifyOptions) -> String;
+
+/// The stages of `process_name`, in order, with the names `explain` reports them under.
+const STAGES: &[(&str, Stage)] = &[
+    // 1. replace unwanted characters with separators; first, so that a removed `~` can't join words that
+    //    the splitting stages would have separated
+    ("replace special characters", replace_special_chars),
+    // 2. insert separators at camelCase and acronym boundaries
+    ("split camelCase", split_camel_case_with),
+    // 3. insert separators at letter/digit boundaries
+    ("split numbers", split_numbers_with),
+    // 4. lowercase
+    ("lowercase", |s, _| s.to_lowercase()),
+    // 5-8. collapse separators and dots
+    ("collapse separators", |s, options| collapse_separators_with(s, options.separator)),
+    // 9. trim leading and trailing separators
+    ("trim separators", trim_separators),
+];
+
+fn process_name(name: &str, options: &DashifyOptions) -> String {
+    process_name_traced(name, options, &mut Trace::Off)
+}
+
+fn process_name_traced(name: &str, options: &DashifyOptions, trace: &mut Trace) -> String {
+    let mut result = name.to_string();
+    for (stage_name, stage) in STAGES {
+        let next = stage(&result, options);
+        trace.record(stage_name, &result, &next);
+        result = next;
+    }
+    // with `.` as the separator the trim stage also takes a hidden file's leading dot
+    if options.separator == '.' && name.starts_with('.') && !result.is_empty() {
+        result.insert(0, '.');
+    }
+    result
+}
+
+fn replace_special_chars(s: &str, options: &DashifyOptions) -> String {
+    s.chars()
+        .filter_map(|c| match c {
+            '~' => match options.tilde_handling {
+                TildeHandling::Preserve => Some(c),
+                TildeHandling::Remove => None,
+                TildeHandling::ToHyphen => Some(options.separator),
+            },
+            _ if DASH_CHARS.contains(&c) || (options.force_dash && c == '_') => Some(options.separator),
+            _ => Some(c),
+        })
+        .collect()
+}
+
+fn trim_separators(s: &str, options: &DashifyOptions) -> String {
+    s.trim_matches(['-', '_', options.separator]).to_string()
+}
+
+// "a-b_c-d" with 2 -> "a-b"; 0 leaves nothing, so dashify fails with EmptyResult
+fn truncate_words(name: &str, max_words: usize, separator: char) -> &str {
+    if max_words == 0 {
+        return "";
+    }
+    name.match_indices(['-', '_', separator])
+        .nth(max_words - 1)
+        .map_or(name, |(i, _)| &name[..i])
+}
+
+// "hello-world" with 8 -> "hello", "helloworld" with 8 -> "hellowor"; cuts at the last separator within the limit,
+// or mid-word if there is none
+fn truncate_length(name: &str, max_length: usize, separator: char) -> &str {
+    let is_separator = |c: char| c == '-' || c == '_' || c == separator;
+    let Some((cut, _)) = name.char_indices().nth(max_length) else {
+        return name;
+    };
+    let head = &name[..cut];
+    let head = if name[cut..].starts_with(is_separator) {
+        head
+    } else {
+        head.rfind(is_separator).map_or(head, |i| &head[..i])
+    };
+    head.trim_end_matches(is_separator)
+}
+
+fn collapse_separators(s: &str) -> String {
+    collapse_separators_with(s, '-')
+}
+
+fn collapse_separators_with(s: &str, separator: char) -> String {
+    // 5. collapse runs of mixed separators into a single one
+    let mut result = collapse_mixed_separators_with(s, separator);
+    // 6. drop separators that touch a dot
+    result = drop_separators_around_dots(&result, separator);
+    // 7. collapse runs of dots, including those that step 6 just brought together
+    result = patterns::collapse_runs(&result, '.');
+    // 8. drop a trailing dot
+    result.trim_end_matches('.').to_string()
+}
+
+fn drop_separators_around_dots(s: &str, separator: char) -> String {
+    let is_separator = |c: char| c != '.' && (c == '-' || c == '_' || c == separator);
+    let mut result = String::with_capacity(s.len());
+    let mut after_dot = false;
+    for c in s.chars() {
+        if c == '.' {
+            while result.ends_with(is_separator) {
+                result.pop();
+            }
+            after_dot = true;
+        } else if after_dot && is_separator(c) {
+            continue;
+        } else {
+            after_dot = false;
+        }
+        result.push(c);
+    }
+    result
+}
+
+// Where `split_camel_case_with` is within the current run of letters.
+#[derive(Clone, Copy)]
+enum CaseState {
+    /// At the start of the string or after anything but a letter, where an uppercase letter never splits.
+    Start,
+    /// After an uppercase letter: the first letter of a word or part of an acronym such as `XML`.
+    InAcronym,
+    /// In a word this many letters long so far, counting a leading capital: `My` and `my` are both 2.
+    InWord(usize),
+    /// After a word from `acronym_dictionary`, where an uppercase letter always starts a new word.
+    AfterKnownWord,
+}
+
+// A lowercase->uppercase boundary only splits once the word before it, capital included, is at least
+// `abbreviation_min_length` letters long, so "MyFile" -> "My-File" but "iPhone" stays whole.
+// An acronym ends before its last capital when a lowercase letter follows, so "XMLParser" -> "XML-Parser", while
+// "AFile" -> "A-File" and an acronym at the end is kept whole, "parseHTML" -> "parse-HTML". Words from
+// `acronym_dictionary` are split off whole: with "GraphQL", "useGraphQLServer" -> "use-GraphQL-Server".
+fn split_camel_case_with(s: &str, options: &DashifyOptions) -> String {
+    let chars: Vec<char> = s.chars().collect();
+    let mut result = String::with_capacity(s.len() + 4);
+    let mut state = CaseState::Start;
+    let mut i = 0;
+    while i < chars.len() {
+        if let Some(end) = known_word_end(&chars, i, &options.acronym_dictionary) {
+            if !matches!(state, CaseState::Start) {
+                result.push(options.separator);
+            }
+            result.extend(&chars[i..end]);
+            state = CaseState::AfterKnownWord;
+            i = end;
+            continue;
+        }
+        let c = chars[i];
+        state = if c.is_ascii_uppercase() {
+            let split = match state {
+                CaseState::Start => false,
+                CaseState::InAcronym => chars.get(i + 1).is_some_and(|n| n.is_ascii_lowercase()),
+                CaseState::InWord(length) => length >= options.abbreviation_min_length,
+                CaseState::AfterKnownWord => true,
+            };
+            if split {
+                result.push(options.separator);
+            }
+            CaseState::InAcronym
+        } else if c.is_ascii_lowercase() {
+            match state {
+                CaseState::InWord(length) => CaseState::InWord(length + 1),
+                // the capital before it starts the word
+                CaseState::InAcronym => CaseState::InWord(2),
+                _ => CaseState::InWord(1),
+            }
+        } else {
+            CaseState::Start
+        };
+        result.push(c);
+        i += 1;
+    }
+    result
+}
+
+// The end of the longest of `words` found at `i`, ignoring case. It has to start where a camelCase word can, at an
+// uppercase letter or after a non-letter, and end where one can, so that it isn't part of a longer lowercase word.
+fn known_word_end(chars: &[char], i: usize, words: &[String]) -> Option<usize> {
+    let starts_word = i == 0 || !chars[i - 1].is_ascii_alphabetic() || chars[i].is_ascii_uppercase();
+    if words.is_empty() || !starts_word {
+        return None;
+    }
+    words
+        .iter()
+        .filter(|word| !word.is_empty())
+        .filter_map(|word| {
+            let end = i + word.chars().count();
+            let matches = chars.get(i..end)?.iter().zip(word.chars()).all(|(c, w)| c.eq_ignore_ascii_case(&w));
+            let ends_word = !chars.get(end).is_some_and(|c| c.is_ascii_lowercase());
+            (matches && ends_word).then_some(end)
+        })
+        .max()
+}
+
+fn split_numbers_with(s: &str, options: &DashifyOptions) -> String {
+    let chars: Vec<char> = s.chars().collect();
+    let mut result = String::with_capacity(s.len() + 4);
+    let mut prev: Option<char> = None;
+    // a hex literal, UUID or date, kept whole
+    let mut token = 0..0;
+    for (i, &c) in chars.iter().enumerate() {
+        if i >= token.end {
+            if let Some(end) = kept_token_end(&chars, i, options) {
+                token = i..end;
+            }
+        }
+        if let Some(p) = prev {
+            let boundary = (p.is_ascii_alphabetic() && c.is_ascii_digit()) || (p.is_ascii_digit() && c.is_ascii_alphabetic());
+            let boundary = boundary && !(i > token.start && i < token.end);
+            let ordinal = boundary && options.preserve_ordinals && p.is_ascii_digit() && {
+                let letters: String = chars[i..].iter().take_while(|c| c.is_ascii_alphabetic()).collect();
+                is_ordinal_suffix(p, &letters)
+            };
+            if boundary && !ordinal {
+                result.push(options.separator);
+            }
+        }
+        result.push(c);
+        prev = Some(c);
+    }
+    result
+}
+
+// Finds the end of a kind of token starting at a given index into the chars.
+type TokenDetector = fn(&[char], usize) -> Option<usize>;
+
+// The end of a token starting at `i` that the options say to keep whole, if there is one.
+fn kept_token_end(chars: &[char], i: usize, options: &DashifyOptions) -> Option<usize> {
+    let detectors: [(bool, TokenDetector); 3] = [
+        (options.preserve_hex, detect_hex_prefix),
+        (options.preserve_uuid, detect_uuid),
+        (options.preserve_dates, detect_date),
+    ];
+    detectors
+        .into_iter()
+        .filter(|&(enabled, _)| enabled)
+        .find_map(|(_, detect)| detect(chars, i))
+}
+
+// The end of the hex literal, e.g. `0xDEAD` or `0X1A2B`, starting at `i`, if there is one. It has to start a word,
+// so the `0x` in `1920x1080` doesn't count.
+fn detect_hex_prefix(chars: &[char], i: usize) -> Option<usize> {
+    let starts_word = i == 0 || !chars[i - 1].is_ascii_alphanumeric();
+    if !starts_word || chars[i] != '0' || !matches!(chars.get(i + 1), Some('x' | 'X')) {
+        return None;
+    }
+    let digits = chars[i + 2..].iter().take_while(|c| c.is_ascii_hexdigit()).count();
+    (digits > 0).then_some(i + 2 + digits)
+}
+
+// The end of the UUID starting at `i`, if there is one, with or without its dashes. It has to be a whole word.
+fn detect_uuid(chars: &[char], i: usize) -> Option<usize> {
+    if i > 0 && chars[i - 1].is_ascii_alphanumeric() {
+        return None;
+    }
+    [36, 32].into_iter().find_map(|len| {
+        let candidate: String = chars.get(i..i + len)?.iter().collect();
+        let ends_word = !chars.get(i + len).is_some_and(|c| c.is_ascii_alphanumeric());
+        (ends_word && looks_like_uuid(&candidate)).then_some(i + len)
+    })
+}
+
+// The end of the date starting at `i`, e.g. `20240115`, `2024-01-15` or `2024Jan15`, if there is one. It has to
+// start a run of digits and can't run on into more digits or letters, so `2024Janet` is no date.
+fn detect_date(chars: &[char], i: usize) -> Option<usize> {
+    if !chars[i].is_ascii_digit() || (i > 0 && chars[i - 1].is_ascii_digit()) {
+        return None;
+    }
+    // ASCII only, so byte offsets into it are char offsets into `chars`
+    let rest: String = chars[i..].iter().take_while(|&&c| c.is_ascii_alphanumeric() || c == '-').collect();
+    let end = i + patterns::date_prefix_len(&rest)?;
+    let last = chars[end - 1];
+    let runs_on = chars.get(end).is_some_and(|&next| {
+        (next.is_ascii_digit() && last.is_ascii_digit()) || (next.is_ascii_alphabetic() && last.is_ascii_alphabetic())
+    });
+    (!runs_on).then_some(end)
+}
+
+// 8-4-4-4-12 hex digits in either case, or the same 32 digits without the dashes
+fn looks_like_uuid(s: &str) -> bool {
+    let groups: Vec<&str> = s.split('-').collect();
+    let lengths: Vec<usize> = groups.iter().map(|group| group.len()).collect();
+    (lengths == [8, 4, 4, 4, 12] || lengths == [32])
+        && groups.iter().all(|group| group.chars().all(|c| c.is_ascii_hexdigit()))
+}
+
+// Whether `suffix` makes a number ending in `digit` an ordinal: 1st, 2nd, 3rd, 4th, and th after any digit for
+// 11th to 13th. `suffix` is the whole run of letters after the digit, so "1stly" is not one.
+fn is_ordinal_suffix(digit: char, suffix: &str) -> bool {
+    let suffix = suffix.to_ascii_lowercase();
+    match digit {
+        '1' => suffix == "st" || suffix == "th",
+        '2' => suffix == "nd" || suffix == "th",
+        '3' => suffix == "rd" || suffix == "th",
+        _ => suffix == "th",
+    }
+}
+
+// Like `collapse_mixed_separators`, with `separator` taking the place of `-`.
+fn collapse_mixed_separators_with(s: &str, separator: char) -> String {
+    let is_separator = |c: char| c == '-' || c == '_' || c == separator;
+    let mut result = String::with_capacity(s.len());
+    let mut chars = s.chars().peekable();
+    while let Some(c) = chars.next() {
+        if is_separator(c) {
+            // INVARIANT: any non-underscore in the run wins, regardless of order (`_-`, `-_`, `_-_` all give `-`).
+            // INVARIANT: only an all-underscore run stays `_`, so snake_case words survive without force_dash.
+            let mut only_underscores = c == '_';
+            while let Some(&next) = chars.peek() {
+                if !is_separator(next) {
+                    break;
+                }
+                only_underscores &= next == '_';
+                chars.next();
+            }
+            // INVARIANT: exactly one separator is emitted per run, so the output never contains `--`, `__`,
+            // `-_` or `_-`, which is what `is_already_clean` relies on.
+            result.push(if only_underscores { '_' } else { separator });
+        } else {
+            result.push(c);
+        }
+    }
+    result
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+pub struct RenameOperation {
+    pub from: PathBuf,
+    pub to: PathBuf,
+}
+
+impl RenameOperation {
+    pub fn new(from: impl Into<PathBuf>, to: impl Into<PathBuf>) -> Self {
+        Self {
+            from: from.into(),
+            to: to.into(),
+        }
+    }
+
+    pub fn is_change(&self) -> bool {
+        self.from != self.to
+    }
+
+    pub fn reversed(&self) -> Self {
+        Self::new(&self.to, &self.from)
+    }
+
+    pub fn apply(&self, options: &DashifyOptions) -> Result<(), DashifyError> {
+        if options.preserve_timestamps {
+            return rename_preserving_timestamps(self);
+        }
+        fs::rename(&self.from, &self.to).map_err(|source| DashifyError::Io {
+            path: self.from.clone(),
+            source,
+        })
+    }
+}
+
+/// Renames `op.from` to `op.to`, then restores the original access and modification times.
+pub fn rename_preserving_timestamps(op: &RenameOperation) -> Result<(), DashifyError> {
+    let io_error = |path: &Path| {
+        let path = path.to_path_buf();
+        move |source| DashifyError::Io { path, source }
+    };
+    let metadata = fs::metadata(&op.from).map_err(io_error(&op.from))?;
+    let atime = FileTime::from_last_access_time(&metadata);
+    let mtime = FileTime::from_last_modification_time(&metadata);
+    fs::rename(&op.from, &op.to).map_err(io_error(&op.from))?;
+    filetime::set_file_times(&op.to, atime, mtime).map_err(io_error(&op.to))
+}
+
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct ProcessingStats {
+    pub processed: u32,
+    pub renamed: u32,
+    pub unchanged: u32,
+    /// Net change in filename length across renames, see `count_bytes_saved`.
+    pub bytes_saved: i64,
+    /// Names left alone because they are not ASCII; only counted with `report_non_ascii_separately`.
+    pub unicode_skipped: u32,
+    pub errors: Vec<(PathBuf, String)>,
+}
+
+impl ProcessingStats {
+    pub fn record(&mut self, op: &RenameOperation) {
+        self.processed += 1;
+        if op.is_change() {
+            self.renamed += 1;
+            self.bytes_saved += count_bytes_saved(std::slice::from_ref(op));
+        } else {
+            self.unchanged += 1;
+        }
+    }
+
+    pub fn record_error(&mut self, path: impl Into<PathBuf>, error: impl ToString) {
+        self.processed += 1;
+        self.errors.push((path.into(), error.to_string()));
+    }
+}
+
+impl fmt::Display for ProcessingStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Processed {} files: {} renamed, {} unchanged, {} errors; ",
+            self.processed,
+            self.renamed,
+            self.unchanged,
+            self.errors.len()
+        )?;
+        if self.bytes_saved < 0 {
+            write!(f, "names are {} bytes longer", -self.bytes_saved)
+        } else {
+            write!(f, "names are {} bytes shorter", self.bytes_saved)
+        }
+    }
+}
+
+/// What came of renaming a batch of files, e.g. the contents of a directory.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct BatchRenameResult {
+    pub renamed: u64,
+    pub unchanged: u64,
+    pub errors: Vec<(PathBuf, String)>,
+    /// Files left under their old names because the new one was taken, with `CollisionStrategy::Skip`.
+    pub skipped: Vec<PathBuf>,
+    /// Renames that got a numbered name because the new one was taken, with `CollisionStrategy::AppendCounter`.
+    /// These are counted in `renamed` too.
+    pub renumbered: Vec<RenameOperation>,
+}
+
+impl BatchRenameResult {
+    pub fn processed(&self) -> u64 {
+        self.renamed + self.unchanged + self.skipped.len() as u64 + self.errors.len() as u64
+    }
+
+    pub fn record(&mut self, op: &RenameOperation) {
+        if op.is_change() {
+            self.renamed += 1;
+        } else {
+            self.unchanged += 1;
+        }
+    }
+
+    pub fn record_error(&mut self, path: impl Into<PathBuf>, error: impl ToString) {
+        self.errors.push((path.into(), error.to_string()));
+    }
+
+    /// Records a file the conflict strategy skipped, rather than counting it as unchanged.
+    pub fn record_skipped(&mut self, path: impl Into<PathBuf>) {
+        self.skipped.push(path.into());
+    }
+
+    /// Records a rename to a numbered name picked by the conflict strategy.
+    pub fn record_renumbered(&mut self, op: &RenameOperation) {
+        self.renamed += 1;
+        self.renumbered.push(op.clone());
+    }
+
+    /// Adds the counts, errors, skips and renumberings of `other` to these.
+    pub fn merge(&mut self, other: BatchRenameResult) {
+        self.renamed += other.renamed;
+        self.unchanged += other.unchanged;
+        self.errors.extend(other.errors);
+        self.skipped.extend(other.skipped);
+        self.renumbered.extend(other.renumbered);
+    }
+}
+
+impl fmt::Display for BatchRenameResult {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "Processed {} files: {} renamed", self.processed(), self.renamed)?;
+        if !self.renumbered.is_empty() {
+            write!(f, " ({} renumbered)", self.renumbered.len())?;
+        }
+        write!(f, ", {} unchanged", self.unchanged)?;
+        if !self.skipped.is_empty() {
+            write!(f, ", {} skipped", self.skipped.len())?;
+        }
+        write!(f, ", {} errors", self.errors.len())
+    }
+}
+
+/// Sums how much shorter the filenames got across `ops`; negative when renaming made them longer overall.
+pub fn count_bytes_saved(ops: &[RenameOperation]) -> i64 {
+    let name_len = |path: &Path| path.file_name().map_or(0, |name| name.len() as i64);
+    ops.iter().map(|op| name_len(&op.from) - name_len(&op.to)).sum()
+}
+
+/// Lowercases every component of `path`, for consistent display on case-insensitive filesystems.
+pub fn normalize_path_case(path: &Path) -> PathBuf {
+    path.components()
+        .map(|component| match component {
+            Component::Normal(name) => PathBuf::from(name.to_string_lossy().to_lowercase()),
+            other => PathBuf::from(other.as_os_str()),
+        })
+        .collect()
+}
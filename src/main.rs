@@ -1,8 +1,25 @@
-use clap::Parser;
+use clap::builder::{PossibleValue, PossibleValuesParser, TypedValueParser};
+use clap::{ArgAction, Parser, ValueHint};
+use dashify::{
+    apply_renames_parallel, compute_rename_plan, dashify_multiple_files_in_transaction, generate_html_report,
+    group_renames_by_directory, is_resource_fork, safe_rename, validate_options, BatchRenameResult, CollisionStrategy,
+    CompiledOptions, ConfigError, DashifyError, DashifyOptions, LogEntry, LogOp, Logger, NoopLogger, ProcessingStats,
+    RenameConflict, RenameOperation, RenamePlan, RenameSession,
+};
+#[cfg(feature = "serde")]
+use dashify::JsonLinesLogger;
+#[cfg(feature = "toml")]
+use dashify::{find_config_file, CONFIG_FILE_NAME};
 use eyre::Result;
-use regex::Regex;
+#[cfg(feature = "serde")]
+use serde::Serialize;
+use std::borrow::Cow;
+#[cfg(unix)]
+use std::ffi::OsStr;
 use std::fs;
+use std::io::{self, BufRead, IsTerminal, Write};
 use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
 
 mod built_info {
     include!(concat!(env!("OUT_DIR"), "/git_describe.rs"));
@@ -17,26 +34,658 @@ struct Args {
     #[arg(short, long, help = "Recursively process files in subdirectories")]
     recursive: bool,
 
-    #[arg(value_name = "PATH", default_value = ".", help = "Path to file or directory to process")]
+    #[arg(
+        long,
+        requires = "recursive",
+        conflicts_with_all = ["output_dir", "parallel"],
+        help = "With --recursive, rename the subdirectories too, deepest first"
+    )]
+    normalize_dirs: bool,
+
+    #[arg(
+        long,
+        value_name = "OUTPUT.html",
+        value_hint = ValueHint::FilePath,
+        help = "Write an HTML report of all rename operations"
+    )]
+    report: Option<PathBuf>,
+
+    #[arg(long, help = "List files that would be renamed without renaming them")]
+    list: bool,
+
+    #[arg(long, requires = "list", help = "With --list, print only the number of files that would be renamed")]
+    count: bool,
+
+    #[arg(long, conflicts_with = "list", help = "Exit non-zero and list the files if any would be renamed")]
+    check: bool,
+
+    #[arg(long, help = "Print how many files were renamed, unchanged, skipped and failed to stderr when done")]
+    stats: bool,
+
+    #[arg(
+        long,
+        value_enum,
+        default_value = "text",
+        conflicts_with = "verbose",
+        help = "Print the renames, and the --stats counts, as text or as one JSON object on stdout"
+    )]
+    format: Format,
+
+    #[arg(long, help = "Like --stats, but also print how many bytes the new names saved")]
+    summary: bool,
+
+    #[arg(
+        long,
+        value_name = "DIR",
+        value_hint = ValueHint::DirPath,
+        help = "Copy files to DIR under their new names, leaving the originals untouched"
+    )]
+    #[cfg_attr(feature = "serde", arg(conflicts_with = "save_session"))]
+    output_dir: Option<PathBuf>,
+
+    #[cfg(feature = "serde")]
+    #[arg(
+        long,
+        value_name = "FILE",
+        value_hint = ValueHint::FilePath,
+        help = "Save the renames made to FILE so they can be undone with --undo"
+    )]
+    save_session: Option<PathBuf>,
+
+    #[cfg(feature = "serde")]
+    #[arg(
+        long,
+        value_name = "PATH",
+        value_hint = ValueHint::FilePath,
+        help = "Append a JSON Lines record of every rename, skip and error to PATH"
+    )]
+    log_file: Option<PathBuf>,
+
+    #[cfg(feature = "serde")]
+    #[arg(
+        long,
+        value_name = "FILE",
+        value_hint = ValueHint::FilePath,
+        help = "Undo the renames saved in FILE by --save-session or --log-file"
+    )]
+    undo: Option<PathBuf>,
+
+    #[arg(long, help = "Read names from stdin and print their dashified forms without touching any files")]
+    stdin: bool,
+
+    #[arg(short = '0', long, help = "With --stdin, read NUL-separated names, as from find -print0")]
+    null: bool,
+
+    #[cfg(feature = "watch")]
+    #[arg(
+        long,
+        conflicts_with_all = ["dry_run", "output_dir", "parallel", "list", "check", "stdin", "normalize_dirs"],
+        help = "Keep running and dashify new files as they appear in the given directories"
+    )]
+    watch: bool,
+
+    #[arg(short = 'n', long, help = "Show what would be renamed without renaming anything")]
+    dry_run: bool,
+
+    #[arg(
+        long,
+        conflicts_with_all = ["dry_run", "output_dir", "parallel", "interactive", "normalize_dirs"],
+        help = "Rename all the files or, if any rename fails, none of them"
+    )]
+    atomic: bool,
+
+    #[arg(
+        short = 'j',
+        long,
+        value_name = "N",
+        num_args = 0..=1,
+        default_missing_value = "0",
+        conflicts_with_all = ["interactive", "output_dir"],
+        help = "Rename on N threads, or one per CPU if N is left out"
+    )]
+    parallel: Option<usize>,
+
+    #[arg(short, long, conflicts_with = "dry_run", help = "Ask before each rename")]
+    interactive: bool,
+
+    #[arg(
+        long,
+        value_name = "STRATEGY",
+        default_value = "error",
+        value_parser = PossibleValuesParser::new([
+            PossibleValue::new("skip"),
+            PossibleValue::new("overwrite"),
+            PossibleValue::new("number").alias("append-counter"),
+            PossibleValue::new("error"),
+        ])
+        .map(|strategy| strategy.parse::<CollisionStrategy>().expect("every listed strategy parses")),
+        help = "What to do when the new name is taken: skip, overwrite, number or error"
+    )]
+    conflict_strategy: CollisionStrategy,
+
+    #[arg(short, long, action = ArgAction::Count, help = "Print each rename, skip and error as it happens")]
+    verbose: u8,
+
+    #[arg(long, help = "Ignore DASHIFY_* settings in .env files")]
+    no_env_file: bool,
+
+    #[cfg(feature = "toml")]
+    #[arg(long, help = "Ignore .dashify.toml in the current directory and the home directory")]
+    no_config: bool,
+
+    #[arg(long, help = "Replace underscores with the separator too")]
+    force_dash: bool,
+
+    #[arg(long, value_name = "CHAR", help = "Character to join words with [default: -]")]
+    separator: Option<char>,
+
+    #[arg(long, help = "Strip non-ASCII characters instead of leaving such names alone")]
+    force_ascii: bool,
+
+    #[arg(long, help = "Transliterate non-ASCII names to ASCII, e.g. café to cafe, instead of leaving them alone")]
+    transliterate: bool,
+
+    #[arg(long, conflicts_with = "transliterate", help = "Remove accents from letters, e.g. naïve to naive")]
+    strip_diacritics: bool,
+
+    #[arg(long, help = "Remove download noise such as 'Copy of' and '(2)'")]
+    strip_noise_words: bool,
+
+    #[cfg(feature = "regex-patterns")]
+    #[arg(long, value_name = "PATTERN", help = "Leave alone names that this regex matches in full; repeatable")]
+    preserve: Vec<String>,
+
+    #[arg(long, value_name = "EXT", help = "Keep this multi-part extension whole, e.g. d.ts; repeatable")]
+    compound_ext: Vec<String>,
+
+    #[arg(long, value_name = "WORD", help = "Don't split this word at its capitals, e.g. GraphQL; repeatable")]
+    acronym: Vec<String>,
+
+    #[arg(long, help = "Keep the original access and modification times")]
+    preserve_timestamps: bool,
+
+    #[arg(
+        value_name = "PATH",
+        default_value = ".",
+        value_hint = ValueHint::AnyPath,
+        help = "Path to file or directory to process"
+    )]
     paths: Vec<String>,
+
+    #[command(subcommand)]
+    command: Option<Command>,
 }
 
+#[derive(clap::Subcommand, Debug)]
+enum Command {
+    /// Report files whose names aren't dashified, without renaming anything; exits 1 if there are any
+    Check {
+        #[arg(short, long, help = "Check files in subdirectories too")]
+        recursive: bool,
+
+        #[arg(long, value_enum, default_value = "text", help = "Print 'not clean: PATH' lines or a JSON array")]
+        format: Format,
+
+        #[arg(
+            value_name = "PATH",
+            default_value = ".",
+            value_hint = ValueHint::AnyPath,
+            help = "Files or directories to check"
+        )]
+        paths: Vec<String>,
+    },
+    /// Print a completion script for SHELL to stdout
+    #[cfg(feature = "completions")]
+    Completions {
+        #[arg(value_enum)]
+        shell: clap_complete::Shell,
+    },
+    /// Manage the .dashify.toml config file
+    #[cfg(feature = "toml")]
+    Config {
+        #[command(subcommand)]
+        action: ConfigAction,
+    },
+}
+
+#[derive(clap::ValueEnum, Clone, Copy, Debug, PartialEq, Eq)]
+enum Format {
+    Text,
+    #[cfg(feature = "serde")]
+    Json,
+}
+
+#[cfg(feature = "toml")]
+#[derive(clap::Subcommand, Debug)]
+enum ConfigAction {
+    /// Write a commented .dashify.toml with the default settings to the current directory
+    Init,
+}
+
+/// Written by `dashify config init`; every setting is commented out, so it starts out with no effect.
+#[cfg(feature = "toml")]
+const CONFIG_TEMPLATE: &str = r#"# dashify settings for this directory; command-line flags override them.
+# Uncomment a setting to change it. The values shown are the defaults, or examples for settings that are off.
+
+# Replace underscores with the separator too
+# force_dash = false
+
+# Character to join words with
+# separator = "-"
+
+# Strip non-ASCII characters instead of leaving such names alone
+# force_ascii = false
+
+# Transliterate non-ASCII names to ASCII, e.g. café to cafe, instead of leaving them alone
+# transliterate = false
+
+# Remove accents from letters, e.g. naïve to naive
+# strip_diacritics = false
+
+# Remove download noise such as 'Copy of' and '(2)'
+# strip_noise_words = false
+
+# What to do with ~ in names: "preserve", "remove" or "to-hyphen"
+# tilde_handling = "remove"
+
+# Lowercase letters needed before an uppercase one starts a new word
+# abbreviation_min_length = 2
+
+# Keep only the first this many words of the name
+# max_stem_words = 4
+
+# Shorten the name to at most this many characters, not counting the extension
+# max_length = 64
+
+# Leave alone names that one of these regexes matches in full; needs the regex-patterns feature
+# preserve = ["Makefile", "Dockerfile"]
+
+# Multi-part extensions to keep whole; setting this replaces the defaults
+# compound_ext = ["tar.gz", "tar.bz2", "tar.xz"]
+
+# Words not to split at their capitals
+# acronym = ["OAuth", "GraphQL"]
+
+# Keep the original access and modification times
+# preserve_timestamps = false
+
+# Also read DASHIFY_* settings from .env files
+# respect_dotenv = true
+"#;
+
 fn main() -> Result<()> {
+    match run() {
+        // dashify's own errors, such as a bad --preserve pattern or config file, are the user's to fix, so skip
+        // the backtrace
+        Err(e) if e.is::<DashifyError>() || e.is::<ConfigError>() => {
+            eprintln!("Error: {e}");
+            std::process::exit(1);
+        }
+        result => result,
+    }
+}
+
+fn run() -> Result<()> {
     let args = Args::parse();
-    for path in &args.paths {
-        let expanded_path = expand_tilde(path);
-        if Path::new(&expanded_path).is_file() {
-            rename_file(&expanded_path)?;
-        } else if Path::new(&expanded_path).is_dir() {
-            rename_files_in_dir(&expanded_path, args.recursive)?;
-        } else {
-            eprintln!("Error: {path} is not a file or directory");
+    match &args.command {
+        Some(Command::Check { recursive, format, paths }) => return check_clean(&args, paths, *recursive, *format),
+        #[cfg(feature = "completions")]
+        Some(Command::Completions { shell }) => {
+            use clap::CommandFactory;
+            clap_complete::generate(*shell, &mut Args::command(), "dashify", &mut io::stdout());
+            return Ok(());
+        }
+        #[cfg(feature = "toml")]
+        Some(Command::Config { action: ConfigAction::Init }) => return init_config(),
+        None => {}
+    }
+    #[cfg(feature = "serde")]
+    if let Some(session) = &args.undo {
+        return undo_session(session);
+    }
+    if args.stdin || args.paths.iter().any(|path| path == "-") {
+        return dashify_stdin(&args);
+    }
+    if args.list {
+        return list_problematic(&args);
+    }
+    if args.check {
+        return check_problematic(&args);
+    }
+    #[cfg(feature = "watch")]
+    if args.watch {
+        return watch(&args);
+    }
+    let mut renamer = Renamer::new(&args, build_options(&args)?, logger(&args)?);
+    let mut result = BatchRenameResult::default();
+    let mut conflicts = Vec::new();
+    if args.atomic {
+        let plans = args
+            .paths
+            .iter()
+            .map(|path| plan_path(path, args.recursive, &renamer.options))
+            .collect::<Result<Vec<_>>>()?;
+        result = renamer.rename_atomically(plans)?;
+    } else {
+        for path in &args.paths {
+            let expanded_path = PathBuf::from(expand_tilde(path));
+            let output = args.output_dir.as_deref();
+            if args.dry_run {
+                let plan = plan_path(path, args.recursive, &renamer.options)?;
+                conflicts.extend(plan.conflicts.iter().cloned());
+                result.merge(renamer.preview(plan, &expanded_path, output)?);
+            } else if let Some(jobs) = args.parallel {
+                result.merge(renamer.rename_in_parallel(plan_path(path, args.recursive, &renamer.options)?, jobs));
+            } else if expanded_path.is_file() {
+                renamer.process_file(&expanded_path, output, &mut result);
+            } else if expanded_path.is_dir() {
+                result.merge(renamer.rename_files_in_dir(&expanded_path, output, args.recursive)?);
+            } else {
+                eprintln!("Error: {path} is not a file or directory");
+                std::process::exit(1);
+            }
+        }
+    }
+    if args.stats && args.format == Format::Text {
+        eprintln!("{result}");
+    }
+    #[cfg(feature = "serde")]
+    if let Some(path) = &args.save_session {
+        renamer.session.save(path)?;
+    }
+    let Renamer { ops, stats, .. } = renamer;
+    if args.summary {
+        eprintln!("{stats}");
+    }
+    match args.format {
+        #[cfg(feature = "serde")]
+        Format::Json => {
+            let report = JsonReport {
+                operations: ops.iter().filter(|op| op.is_change()).collect(),
+                conflicts: &conflicts,
+                stats: args.stats.then_some(&result),
+            };
+            println!("{}", serde_json::to_string(&report)?);
+        }
+        Format::Text if args.dry_run => {
+            print_dry_run(&ops, &conflicts, if args.output_dir.is_some() { "copy" } else { "rename" });
+        }
+        Format::Text => {}
+    }
+    if let Some(report) = &args.report {
+        fs::write(report, generate_html_report(&ops, &stats))?;
+    }
+    if !stats.errors.is_empty() {
+        std::process::exit(1);
+    }
+    Ok(())
+}
+
+/// Renames new files in the given directories as they settle, until interrupted or terminated.
+#[cfg(feature = "watch")]
+fn watch(args: &Args) -> Result<()> {
+    use std::sync::atomic::{AtomicBool, Ordering};
+    use std::sync::Arc;
+
+    let dirs: Vec<PathBuf> = args.paths.iter().map(|path| PathBuf::from(expand_tilde(path))).collect();
+    if let Some(dir) = dirs.iter().find(|dir| !dir.is_dir()) {
+        eprintln!("Error: {} is not a directory", dir.display());
+        std::process::exit(1);
+    }
+    let stop = Arc::new(AtomicBool::new(false));
+    let handler_stop = Arc::clone(&stop);
+    ctrlc::set_handler(move || handler_stop.store(true, Ordering::Relaxed))?;
+
+    let options = build_options(args)?;
+    let mut renamer = Renamer::new(args, options.clone(), logger(args)?);
+    let mut result = BatchRenameResult::default();
+    dashify::watch_for_new_files(
+        &dirs,
+        args.recursive,
+        &options,
+        &stop,
+        |path| renamer.process_file(path, None, &mut result),
+        |e| eprintln!("warning: {e}"),
+    )?;
+
+    if args.stats {
+        eprintln!("{result}");
+    }
+    #[cfg(feature = "serde")]
+    if let Some(path) = &args.save_session {
+        renamer.session.save(path)?;
+    }
+    Ok(())
+}
+
+/// Logs to `--log-file` as JSON Lines, or nowhere without it.
+#[cfg(feature = "serde")]
+fn logger(args: &Args) -> Result<Box<dyn Logger>> {
+    Ok(match &args.log_file {
+        Some(path) => Box::new(JsonLinesLogger::open(path)?),
+        None => Box::new(NoopLogger),
+    })
+}
+
+/// Without the `serde` feature there is no `--log-file` to log to.
+#[cfg(not(feature = "serde"))]
+fn logger(_args: &Args) -> Result<Box<dyn Logger>> {
+    Ok(Box::new(NoopLogger))
+}
+
+#[cfg(feature = "serde")]
+fn undo_session(path: &Path) -> Result<()> {
+    let session = RenameSession::load(path)?;
+    match session.rollback() {
+        Ok(restored) => {
+            println!("restored {} file(s)", restored.len());
+            Ok(())
+        }
+        Err(e) => {
+            println!("restored {} file(s)", e.restored.len());
+            eprintln!("Error: {e}");
             std::process::exit(1);
         }
     }
+}
+
+/// Prints the dashified form of each name read from stdin, keeping any directories in front of it. Names that
+/// dashify fails on are printed as they are, with the error on stderr. On Unix names are read and written as raw
+/// bytes, so ones that aren't UTF-8 pass through intact.
+fn dashify_stdin(args: &Args) -> Result<()> {
+    let options = build_options(args)?;
+    let terminator = if args.null { b'\0' } else { b'\n' };
+    let mut stdout = io::BufWriter::new(io::stdout().lock());
+    let mut failed = false;
+    for record in io::stdin().lock().split(terminator) {
+        let record = record?;
+        let name = if args.null { &record[..] } else { record.strip_suffix(b"\r").unwrap_or(&record) };
+        let name = path_from_bytes(name);
+        let dashified = match options.dashify_path(&name) {
+            Ok(path) => path,
+            Err(DashifyError::NoFilename { .. }) => name.into_owned(),
+            Err(e) => {
+                eprintln!("Error: {e}");
+                failed = true;
+                name.into_owned()
+            }
+        };
+        stdout.write_all(&path_bytes(&dashified))?;
+        stdout.write_all(&[terminator])?;
+    }
+    stdout.flush()?;
+    if failed {
+        std::process::exit(1);
+    }
+    Ok(())
+}
+
+#[cfg(unix)]
+fn path_from_bytes(bytes: &[u8]) -> Cow<'_, Path> {
+    use std::os::unix::ffi::OsStrExt;
+    Cow::Borrowed(Path::new(OsStr::from_bytes(bytes)))
+}
+
+#[cfg(not(unix))]
+fn path_from_bytes(bytes: &[u8]) -> Cow<'_, Path> {
+    Cow::Owned(PathBuf::from(String::from_utf8_lossy(bytes).into_owned()))
+}
+
+#[cfg(unix)]
+fn path_bytes(path: &Path) -> Cow<'_, [u8]> {
+    use std::os::unix::ffi::OsStrExt;
+    Cow::Borrowed(path.as_os_str().as_bytes())
+}
+
+#[cfg(not(unix))]
+fn path_bytes(path: &Path) -> Cow<'_, [u8]> {
+    match path.to_string_lossy() {
+        Cow::Borrowed(path) => Cow::Borrowed(path.as_bytes()),
+        Cow::Owned(path) => Cow::Owned(path.into_bytes()),
+    }
+}
+
+fn list_problematic(args: &Args) -> Result<()> {
+    let problematic = collect_problematic(args)?;
+    if args.count {
+        println!("{}", problematic.len());
+    } else {
+        for path in &problematic {
+            println!("{}", path.display());
+        }
+    }
+    Ok(())
+}
+
+fn check_problematic(args: &Args) -> Result<()> {
+    let problematic = collect_problematic(args)?;
+    for path in &problematic {
+        println!("{}", path.display());
+    }
+    if !problematic.is_empty() {
+        std::process::exit(1);
+    }
+    Ok(())
+}
+
+/// `dashify check`: prints every file under `paths` whose name `is_dashified` rejects, and exits 1 if there are any.
+/// Unlike `--check` it doesn't plan renames, so a file whose new name is taken is still reported.
+///
+/// As a pre-commit hook, `.git/hooks/pre-commit`, rejecting commits that add badly named files:
+///
+/// ```sh
+/// #!/bin/sh
+/// git diff --cached --name-only --diff-filter=A -z | xargs -0 -r dashify check
+/// ```
+fn check_clean(args: &Args, paths: &[String], recursive: bool, format: Format) -> Result<()> {
+    let options = build_options(args)?;
+    let mut not_clean = Vec::new();
+    for path in paths {
+        let walker = WalkDir::new(expand_tilde(path));
+        let walker = if recursive { walker } else { walker.max_depth(1) };
+        for entry in walker {
+            let entry = entry?;
+            if !entry.file_type().is_file() {
+                continue;
+            }
+            let file_name = entry.file_name().to_string_lossy();
+            if options.skip_resource_forks && is_resource_fork(&file_name) {
+                continue;
+            }
+            if !options.is_path_dashified(entry.path()) {
+                not_clean.push(entry.into_path());
+            }
+        }
+    }
+    match format {
+        Format::Text => {
+            for path in &not_clean {
+                println!("not clean: {}", path.display());
+            }
+        }
+        #[cfg(feature = "serde")]
+        Format::Json => {
+            let paths: Vec<_> = not_clean.iter().map(|path| path.to_string_lossy()).collect();
+            println!("{}", serde_json::to_string(&paths)?);
+        }
+    }
+    if !not_clean.is_empty() {
+        std::process::exit(1);
+    }
+    Ok(())
+}
+
+fn collect_problematic(args: &Args) -> Result<Vec<PathBuf>> {
+    let options = build_options(args)?;
+    let mut problematic = Vec::new();
+    for path in &args.paths {
+        problematic.extend(plan_path(path, args.recursive, &options)?.needs_renaming());
+    }
+    Ok(problematic)
+}
+
+/// The plan for a file named on the command line, or for the files of a directory.
+fn plan_path(path: &str, recursive: bool, options: &DashifyOptions) -> Result<RenamePlan> {
+    let expanded_path = PathBuf::from(expand_tilde(path));
+    if expanded_path.is_file() {
+        Ok(RenamePlan::for_files(&[expanded_path], options))
+    } else if expanded_path.is_dir() {
+        Ok(compute_rename_plan(&expanded_path, recursive, options)?)
+    } else {
+        eprintln!("Error: {path} is not a file or directory");
+        std::process::exit(1);
+    }
+}
+
+/// Writes `CONFIG_TEMPLATE` to `.dashify.toml` in the current directory, unless that file already exists.
+#[cfg(feature = "toml")]
+fn init_config() -> Result<()> {
+    let path = Path::new(CONFIG_FILE_NAME);
+    let mut file = match fs::OpenOptions::new().write(true).create_new(true).open(path) {
+        Ok(file) => file,
+        Err(e) if e.kind() == io::ErrorKind::AlreadyExists => {
+            eprintln!("Error: {CONFIG_FILE_NAME} already exists");
+            std::process::exit(1);
+        }
+        Err(e) => return Err(e.into()),
+    };
+    file.write_all(CONFIG_TEMPLATE.as_bytes())?;
+    println!("wrote {CONFIG_FILE_NAME}");
     Ok(())
 }
 
+/// The config file, unless disabled, as the base; then `.env` settings, unless disabled; then the command-line
+/// flags on top.
+fn build_options(args: &Args) -> Result<CompiledOptions<'static>> {
+    let dir = std::env::current_dir()?;
+    let mut options = DashifyOptions::default();
+    #[cfg(feature = "toml")]
+    if !args.no_config {
+        if let Some(path) = find_config_file(&dir) {
+            options = DashifyOptions::from_config_file(&path)?;
+        }
+    }
+    if !args.no_env_file && options.respect_dotenv {
+        for e in options.apply_dotenv(&dir) {
+            eprintln!("warning: {e}");
+        }
+    }
+    options.force_dash |= args.force_dash;
+    options.force_ascii |= args.force_ascii;
+    options.transliterate |= args.transliterate;
+    options.strip_diacritics |= args.strip_diacritics;
+    options.strip_noise_words |= args.strip_noise_words;
+    options.preserve_timestamps |= args.preserve_timestamps;
+    #[cfg(feature = "regex-patterns")]
+    options.preserve_patterns.extend(args.preserve.iter().cloned());
+    options.compound_extensions.extend(args.compound_ext.iter().cloned());
+    options.acronym_dictionary.extend(args.acronym.iter().cloned());
+    if let Some(separator) = args.separator {
+        options.separator = separator;
+    }
+    Ok(validate_options(&options)?.into_owned())
+}
+
 fn expand_tilde(path: &str) -> String {
     if let Some(home) = dirs::home_dir() {
         if path.starts_with("~") {
@@ -46,34 +695,377 @@ fn expand_tilde(path: &str) -> String {
     path.to_string()
 }
 
-fn rename_file(path: &str) -> Result<()> {
-    let path_buf = PathBuf::from(path);
-    if let Some(file_name) = path_buf.file_name() {
-        let file_name = file_name.to_string_lossy();
+fn print_dry_run(ops: &[RenameOperation], conflicts: &[RenameConflict], action: &str) {
+    let name = |path: &Path| path.file_name().unwrap_or_default().to_string_lossy().into_owned();
+    let changes = ops.iter().filter(|op| op.is_change()).cloned().collect();
+    for (dir, ops) in group_renames_by_directory(changes) {
+        println!("{}:", dir.display());
+        for op in ops {
+            println!("  would {action}: {} → {}", name(&op.from), name(&op.to));
+        }
+    }
+    for conflict in conflicts {
+        let sources: Vec<_> = conflict.sources.iter().map(|path| name(path)).collect();
+        println!("conflict: {} → {}", sources.join(", "), conflict.target.display());
+    }
+}
 
-        let re = Regex::new(r"[,_ ]|\\(|\\)")?;
-        let mut new_file_name = re.replace_all(&file_name, "-").to_string();
+/// What `--format json` prints: the renames made, or with `--dry-run` the ones that would be, and with `--stats`
+/// the counts.
+#[cfg(feature = "serde")]
+#[derive(Serialize)]
+struct JsonReport<'a> {
+    operations: Vec<&'a RenameOperation>,
+    #[serde(skip_serializing_if = "<[_]>::is_empty")]
+    conflicts: &'a [RenameConflict],
+    #[serde(skip_serializing_if = "Option::is_none")]
+    stats: Option<&'a BatchRenameResult>,
+}
 
-        let re_hyphens = Regex::new(r"-+")?;
-        new_file_name = re_hyphens.replace_all(&new_file_name, "-").to_string();
-        new_file_name = new_file_name.trim_matches('-').to_string();
-        new_file_name = new_file_name.to_lowercase();
+/// How the conflict strategy settled a file's new name.
+enum Resolution {
+    /// The file got its new name, or kept its own because that is already dashified.
+    Planned,
+    /// The new name, `taken`, was already in use, so the file kept its old one.
+    Skipped { taken: PathBuf },
+    /// The new name was already in use, so the file got a numbered one.
+    Renumbered,
+}
 
-        let new_path = path_buf.with_file_name(new_file_name);
-        fs::rename(path_buf, new_path)?;
+/// Renames files one at a time, or copies them into an output directory, collecting what happened for the
+/// summary, report and log.
+struct Renamer {
+    options: CompiledOptions<'static>,
+    conflict_strategy: CollisionStrategy,
+    dry_run: bool,
+    interactive: bool,
+    normalize_dirs: bool,
+    verbosity: u8,
+    ops: Vec<RenameOperation>,
+    stats: ProcessingStats,
+    session: RenameSession,
+    logger: Box<dyn Logger>,
+}
+
+impl Renamer {
+    fn new(args: &Args, options: CompiledOptions<'static>, logger: Box<dyn Logger>) -> Self {
+        if args.interactive && !io::stdin().is_terminal() {
+            eprintln!("warning: stdin is not a terminal; --interactive will skip every rename");
+        }
+        Self {
+            options,
+            conflict_strategy: args.conflict_strategy,
+            dry_run: args.dry_run,
+            interactive: args.interactive,
+            normalize_dirs: args.normalize_dirs,
+            verbosity: args.verbose,
+            ops: Vec::new(),
+            stats: ProcessingStats::default(),
+            session: RenameSession::new(),
+            logger,
+        }
+    }
+
+    /// With `output`, the file is copied into that directory instead of being renamed.
+    fn process_file(&mut self, path: &Path, output: Option<&Path>, result: &mut BatchRenameResult) {
+        match self.rename_file(path, output) {
+            Ok((op, resolution)) => self.record(path, Ok(op), resolution, output, result),
+            Err(e) => self.record(path, Err(e), Resolution::Planned, output, result),
+        }
+    }
+
+    /// Records what `plan` would do without doing it. With `output` the new names are placed under that directory,
+    /// as `rename_files_in_dir` would copy them; `root` is the file or directory the plan was made for.
+    fn preview(&mut self, plan: RenamePlan, root: &Path, output: Option<&Path>) -> Result<BatchRenameResult> {
+        let mut result = BatchRenameResult::default();
+        let base = if root.is_dir() { root } else { root.parent().unwrap_or(root) };
+        let unchanged = plan.unchanged.into_iter().map(|path| RenameOperation::new(&path, &path));
+        for mut op in plan.operations.into_iter().chain(unchanged) {
+            if let Some(output) = output {
+                let dir = op.from.parent().and_then(|dir| dir.strip_prefix(base).ok()).unwrap_or(Path::new(""));
+                op.to = output.join(dir).join(op.to.file_name().unwrap_or_default());
+            }
+            let path = op.from.clone();
+            self.record(&path, Ok(op), Resolution::Planned, output, &mut result);
+        }
+        if self.normalize_dirs {
+            for entry in WalkDir::new(root).min_depth(1).contents_first(true) {
+                let entry = entry?;
+                if entry.file_type().is_dir() {
+                    let path = entry.path();
+                    let new_path = path.with_file_name(self.options.dashify_dir_name(entry.file_name()));
+                    let op = RenameOperation::new(path, new_path);
+                    self.record(path, Ok(op), Resolution::Planned, None, &mut result);
+                }
+            }
+        }
+        Ok(result)
+    }
+
+    /// Renames the files of `plans` with `dashify_multiple_files_in_transaction`, so either all of them are
+    /// renamed or none are.
+    fn rename_atomically(&mut self, plans: Vec<RenamePlan>) -> Result<BatchRenameResult> {
+        let mut result = BatchRenameResult::default();
+        let mut paths = Vec::new();
+        for plan in plans {
+            paths.extend(plan.needs_renaming());
+            paths.extend(plan.unchanged);
+        }
+        let final_paths = dashify_multiple_files_in_transaction(&paths, &self.options)?;
+        for (path, final_path) in paths.iter().zip(final_paths) {
+            let op = RenameOperation::new(path, final_path);
+            self.record(path, Ok(op), Resolution::Planned, None, &mut result);
+        }
+        Ok(result)
+    }
+
+    /// Applies the plan's conflict-free renames on `jobs` threads, then handles its conflicts one at a time
+    /// according to the conflict strategy.
+    fn rename_in_parallel(&mut self, plan: RenamePlan, jobs: usize) -> BatchRenameResult {
+        let mut result = BatchRenameResult::default();
+        let outcomes = apply_renames_parallel(&plan.operations, jobs, &self.options);
+        for (op, outcome) in plan.operations.into_iter().zip(outcomes) {
+            let path = op.from.clone();
+            let outcome = outcome.map(|()| op).map_err(Into::into);
+            self.record(&path, outcome, Resolution::Planned, None, &mut result);
+        }
+        for path in plan.unchanged {
+            let op = RenameOperation::new(&path, &path);
+            self.record(&path, Ok(op), Resolution::Planned, None, &mut result);
+        }
+        for source in plan.conflicts.iter().flat_map(|conflict| &conflict.sources) {
+            self.process_file(source, None, &mut result);
+        }
+        result
+    }
+
+    fn record(
+        &mut self,
+        path: &Path,
+        outcome: Result<RenameOperation>,
+        resolution: Resolution,
+        output: Option<&Path>,
+        result: &mut BatchRenameResult,
+    ) {
+        match outcome {
+            Ok(op) => {
+                if self.verbosity > 0 && !self.dry_run {
+                    if let Resolution::Skipped { taken } = &resolution {
+                        println!("skipped: {} ({} is taken)", op.from.display(), taken.display());
+                    } else if op.is_change() {
+                        let done = if output.is_some() { "copied" } else { "renamed" };
+                        println!("{done}: {} → {}", op.from.display(), op.to.display());
+                    } else {
+                        println!("skipped: {} (unchanged)", op.from.display());
+                    }
+                }
+                if !self.dry_run {
+                    let (log_op, to) = match (op.is_change(), output) {
+                        (true, Some(_)) => (LogOp::Copy, Some(op.to.as_path())),
+                        (true, None) => (LogOp::Rename, Some(op.to.as_path())),
+                        (false, _) => (LogOp::Skip, None),
+                    };
+                    self.log(&LogEntry { op: log_op, from: &op.from, to, error: None });
+                }
+                self.stats.record(&op);
+                match resolution {
+                    Resolution::Planned => result.record(&op),
+                    Resolution::Skipped { .. } => result.record_skipped(&op.from),
+                    Resolution::Renumbered => result.record_renumbered(&op),
+                }
+                if !self.dry_run && output.is_none() {
+                    self.session.record(&op);
+                }
+                self.ops.push(op);
+            }
+            Err(e) => {
+                if self.verbosity > 0 {
+                    eprintln!("error: {} — {e}", path.display());
+                } else {
+                    let action = if output.is_some() { "copy" } else { "rename" };
+                    eprintln!("Error: could not {action} {}: {e}", path.display());
+                }
+                let error = e.to_string();
+                self.log(&LogEntry {
+                    op: LogOp::Error,
+                    from: path,
+                    to: None,
+                    error: Some(&error),
+                });
+                result.record_error(path, &error);
+                self.stats.record_error(path, error);
+            }
+        }
+    }
+
+    fn log(&mut self, entry: &LogEntry<'_>) {
+        if let Err(e) = self.logger.log(entry) {
+            eprintln!("warning: could not write to the log file: {e}");
+        }
+    }
+
+    fn rename_file(&self, path: &Path, output: Option<&Path>) -> Result<(RenameOperation, Resolution)> {
+        let mut new_path = self.options.dashify_path(path)?;
+        if let Some(dir) = output {
+            new_path = dir.join(new_path.file_name().unwrap_or_default());
+        }
+        if new_path == path {
+            return Ok((RenameOperation::new(path, new_path), Resolution::Planned));
+        }
+        let action = if output.is_some() { "copy" } else { "rename" };
+        if self.interactive && !confirm(action, &path.to_string_lossy(), &new_path.to_string_lossy()) {
+            return Ok((RenameOperation::new(path, path), Resolution::Planned));
+        }
+        let final_path = match output {
+            Some(dir) => {
+                fs::create_dir_all(dir)?;
+                self.options.copy_file_with_conflict_strategy(path, dir, self.conflict_strategy)?
+            }
+            None => self.options.rename_file_with_conflict_strategy(path, self.conflict_strategy)?,
+        };
+        let resolution = if final_path == new_path {
+            Resolution::Planned
+        } else if final_path == path {
+            Resolution::Skipped { taken: new_path }
+        } else {
+            Resolution::Renumbered
+        };
+        Ok((RenameOperation::new(path, final_path), resolution))
+    }
+
+    /// With `output`, files are copied into it and subdirectories recreated under it, keeping their names.
+    fn rename_files_in_dir(
+        &mut self,
+        dir: &Path,
+        output: Option<&Path>,
+        recursive: bool,
+    ) -> Result<BatchRenameResult> {
+        let mut result = BatchRenameResult::default();
+        for entry in fs::read_dir(dir)? {
+            let entry = entry?;
+            let path = entry.path();
+            if path.is_file() {
+                self.process_file(&path, output, &mut result);
+            } else if recursive && path.is_dir() {
+                let output = output.map(|output| output.join(entry.file_name()));
+                result.merge(self.rename_files_in_dir(&path, output.as_deref(), true)?);
+                if self.normalize_dirs {
+                    let outcome = self.rename_dir(&path);
+                    self.record(&path, outcome, Resolution::Planned, None, &mut result);
+                }
+            }
+        }
+        Ok(result)
+    }
+
+    /// Renames a directory whose contents have already been processed. Its parents are left for the callers
+    /// further up, so directories are renamed deepest first.
+    fn rename_dir(&self, path: &Path) -> Result<RenameOperation> {
+        let Some(name) = path.file_name() else {
+            return Ok(RenameOperation::new(path, path));
+        };
+        let new_path = path.with_file_name(self.options.dashify_dir_name(name));
+        if new_path == path {
+            return Ok(RenameOperation::new(path, new_path));
+        }
+        if self.interactive && !confirm("rename", &path.to_string_lossy(), &new_path.to_string_lossy()) {
+            return Ok(RenameOperation::new(path, path));
+        }
+        safe_rename(path, &new_path)?;
+        Ok(RenameOperation::new(path, new_path))
     }
-    Ok(())
 }
 
-fn rename_files_in_dir(dir: &str, recursive: bool) -> Result<()> {
-    for entry in fs::read_dir(dir)? {
-        let entry = entry?;
-        let path = entry.path();
-        if path.is_file() {
-            rename_file(&path.to_string_lossy())?;
-        } else if recursive && path.is_dir() {
-            rename_files_in_dir(&path.to_string_lossy(), true)?;
+/// Asks on stderr whether to `action` (rename or copy) `from` to `to`; anything but `y` or `yes`, or a stdin that
+/// isn't a terminal, means no.
+fn confirm(action: &str, from: &str, to: &str) -> bool {
+    let stdin = io::stdin();
+    if !stdin.is_terminal() {
+        return false;
+    }
+    eprint!("{action} '{from}' to '{to}'? [y/N] ");
+    let _ = io::stderr().flush();
+    let mut answer = String::new();
+    if stdin.lock().read_line(&mut answer).is_err() {
+        return false;
+    }
+    matches!(answer.trim().to_lowercase().as_str(), "y" | "yes")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use clap::CommandFactory;
+    use dashify::{generate_completion_script, Shell};
+    use std::collections::BTreeSet;
+
+    // `Args` with the help and version flags and the help subcommand clap adds
+    fn command() -> clap::Command {
+        let mut command = Args::command();
+        command.build();
+        command
+    }
+
+    fn subcommands(command: &clap::Command) -> impl Iterator<Item = &clap::Command> {
+        command.get_subcommands().filter(|subcommand| subcommand.get_name() != "help")
+    }
+
+    fn flags(command: &clap::Command) -> BTreeSet<(Option<char>, String)> {
+        command
+            .get_arguments()
+            .filter_map(|arg| Some((arg.get_short(), arg.get_long()?.to_string())))
+            .collect()
+    }
+
+    // the flags the fish script completes under `condition`, which is one line each
+    fn fish_flags(script: &str, condition: &str) -> BTreeSet<(Option<char>, String)> {
+        script
+            .lines()
+            .filter(|line| line.contains(&format!(" -n '{condition}' ")))
+            .filter_map(|line| {
+                let short = line.split(" -s ").nth(1).and_then(|rest| rest.chars().next());
+                let long = line.split(" -l ").nth(1)?.split(' ').next()?;
+                Some((short, long.to_string()))
+            })
+            .collect()
+    }
+
+    // whether `script` has `word` on its own, not as the start of a longer flag
+    fn mentions(script: &str, word: &str) -> bool {
+        script.match_indices(word).any(|(i, _)| {
+            !script[i + word.len()..].starts_with(|c: char| c.is_ascii_alphanumeric() || c == '-')
+        })
+    }
+
+    #[test]
+    fn completion_flags_match_the_cli() {
+        let command = command();
+        let fish = generate_completion_script(Shell::Fish, "dashify");
+        let names: Vec<&str> = subcommands(&command).map(|subcommand| subcommand.get_name()).collect();
+        let top_level = format!("not __fish_seen_subcommand_from {}", names.join(" "));
+        assert_eq!(fish_flags(&fish, &top_level), flags(&command));
+        for subcommand in subcommands(&command) {
+            let condition = format!("__fish_seen_subcommand_from {}", subcommand.get_name());
+            assert_eq!(fish_flags(&fish, &condition), flags(subcommand), "{}", subcommand.get_name());
+        }
+    }
+
+    #[test]
+    fn completion_scripts_offer_every_flag_and_subcommand() {
+        let command = command();
+        let mut longs: Vec<String> = flags(&command).into_iter().map(|(_, long)| long).collect();
+        for subcommand in subcommands(&command) {
+            longs.extend(flags(subcommand).into_iter().map(|(_, long)| long));
+        }
+        for shell in [Shell::Bash, Shell::Zsh, Shell::Fish] {
+            let script = generate_completion_script(shell, "dashify");
+            for long in &longs {
+                let flag = if shell == Shell::Fish { format!("-l {long}") } else { format!("--{long}") };
+                assert!(mentions(&script, &flag), "{shell:?} completion is missing {flag}");
+            }
+            for name in subcommands(&command).map(|subcommand| subcommand.get_name()) {
+                assert!(mentions(&script, name), "{shell:?} completion is missing {name}");
+            }
         }
     }
-    Ok(())
 }
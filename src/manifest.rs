@@ -0,0 +1,42 @@
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::{compile, DashifyOptions, RenameOperation};
+
+/// Reads a rename manifest, a CSV file with an `original_path` column and an optional `custom_new_name` column,
+/// e.g. exported from a spreadsheet.
+///
+/// Each row becomes a rename of `original_path` to `custom_new_name` in the same directory, or to the dashified
+/// name when `custom_new_name` is missing or empty; rows whose name `dashify` fails on are skipped. Nothing is
+/// renamed.
+pub fn batch_rename_from_csv(csv_path: &Path, options: &DashifyOptions) -> csv::Result<Vec<RenameOperation>> {
+    let mut reader = csv::ReaderBuilder::new().trim(csv::Trim::Headers).from_path(csv_path)?;
+    let headers = reader.headers()?.clone();
+    let column = |name: &str| headers.iter().position(|header| header == name);
+    let Some(original_column) = column("original_path") else {
+        return Err(io::Error::new(io::ErrorKind::InvalidData, "missing original_path column").into());
+    };
+    let custom_column = column("custom_new_name");
+
+    let compiled = compile(options).ok();
+    let dashified = |name: &str| compiled.as_ref()?.dashify(name).ok();
+    let mut ops = Vec::new();
+    for record in reader.records() {
+        let record = record?;
+        let Some(original) = record.get(original_column).filter(|path| !path.is_empty()) else {
+            continue;
+        };
+        let from = PathBuf::from(original);
+        let custom = custom_column.and_then(|i| record.get(i)).filter(|name| !name.is_empty());
+        let to = match (custom, from.file_name()) {
+            (Some(name), _) => from.with_file_name(name),
+            (None, Some(file_name)) => match dashified(&file_name.to_string_lossy()) {
+                Some(name) => from.with_file_name(name),
+                None => continue,
+            },
+            (None, None) => continue,
+        };
+        ops.push(RenameOperation::new(from, to));
+    }
+    Ok(ops)
+}
@@ -0,0 +1,176 @@
+//! Matchers for the fixed patterns the crate uses, written out by hand so only user-supplied patterns need the
+//! `regex` dependency (see the `regex-patterns` feature). Each documents the regex it stands for.
+
+// the full names come before their abbreviations, so the first match is the longest
+const MONTH_NAMES: [&str; 24] = [
+    "january", "jan", "february", "feb", "march", "mar", "april", "apr", "may", "june", "jun", "july", "jul",
+    "august", "aug", "september", "sept", "sep", "october", "oct", "november", "nov", "december", "dec",
+];
+
+/// `name` without a leading `(?i)copy of\s+`.
+pub(crate) fn strip_copy_of_prefix(name: &str) -> &str {
+    let Some(rest) = strip_prefix_ignore_case(name, "copy of") else {
+        return name;
+    };
+    let words = rest.trim_start();
+    if words.len() < rest.len() {
+        words
+    } else {
+        name
+    }
+}
+
+/// `name` without a trailing `(?i)\s+-\s+copy(\s*\(\d+\))?`.
+pub(crate) fn strip_copy_suffix(name: &str) -> &str {
+    let mut rest = name;
+    if let Some((before, counter)) = name.strip_suffix(')').and_then(|inner| inner.rsplit_once('(')) {
+        if is_number(counter) {
+            rest = before.trim_end();
+        }
+    }
+    let Some(rest) = strip_suffix_ignore_case(rest, "copy") else {
+        return name;
+    };
+    let Some(rest) = strip_whitespace_end(rest).and_then(|rest| rest.strip_suffix('-')) else {
+        return name;
+    };
+    strip_whitespace_end(rest).unwrap_or(name)
+}
+
+/// `(?i)^(final|v\d+|\(\d+\)|\[\d+\]|[-_]+)$`
+pub(crate) fn is_noise_word(word: &str) -> bool {
+    let enclosed = |open, close| word.strip_prefix(open).and_then(|word| word.strip_suffix(close));
+    word.eq_ignore_ascii_case("final")
+        || word.strip_prefix(['v', 'V']).is_some_and(is_number)
+        || enclosed('(', ')').is_some_and(is_number)
+        || enclosed('[', ']').is_some_and(is_number)
+        || (!word.is_empty() && word.chars().all(|c| c == '-' || c == '_'))
+}
+
+/// `^[A-Z_][A-Z0-9_]*$`
+pub(crate) fn is_env_var(name: &str) -> bool {
+    let mut chars = name.chars();
+    chars.next().is_some_and(|c| c.is_ascii_uppercase() || c == '_')
+        && chars.all(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || c == '_')
+}
+
+/// `^v?\d+\.\d+\.\d+`
+pub(crate) fn has_semver_prefix(name: &str) -> bool {
+    let rest = name.strip_prefix('v').unwrap_or(name);
+    skip_number(rest)
+        .and_then(|rest| rest.strip_prefix('.'))
+        .and_then(skip_number)
+        .and_then(|rest| rest.strip_prefix('.'))
+        .and_then(skip_number)
+        .is_some()
+}
+
+/// `^[a-z0-9]+(-[a-z0-9]+)*$`
+pub(crate) fn is_slug(s: &str) -> bool {
+    s.split('-')
+        .all(|word| !word.is_empty() && word.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit()))
+}
+
+/// `^[a-z0-9]([-a-z0-9]*[a-z0-9])?$`
+pub(crate) fn is_k8s_name(s: &str) -> bool {
+    !s.is_empty()
+        && !s.starts_with('-')
+        && !s.ends_with('-')
+        && s.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-')
+}
+
+/// Replaces every run of `c` in `s` with a single one.
+pub(crate) fn collapse_runs(s: &str, c: char) -> String {
+    let mut result = String::with_capacity(s.len());
+    for next in s.chars() {
+        if next != c || !result.ends_with(c) {
+            result.push(next);
+        }
+    }
+    result
+}
+
+/// The length of the date `s` starts with: YYYYMM, YYYYMMDD, YYYY-MM-DD, YYYYMon[D]D or [D]DMonYYYY, with
+/// English month names or their abbreviations in any case. The first of these forms that matches wins.
+pub(crate) fn date_prefix_len(s: &str) -> Option<usize> {
+    if let Some(year) = year(s) {
+        let rest = &s[year..];
+        if let Some(month) = month(rest) {
+            return Some(year + month + day(&rest[month..]).unwrap_or(0));
+        }
+        if let Some(dashed) = rest.strip_prefix('-') {
+            let month = month(dashed);
+            if let Some(day) = month.and_then(|month| dashed[month..].strip_prefix('-')).and_then(day) {
+                return Some(year + month.unwrap_or(0) + day + 2);
+            }
+        }
+        if let Some(month) = month_name(rest) {
+            let rest = &rest[month..];
+            return Some(year + month + day(rest).or_else(|| short_day(rest)).unwrap_or(0));
+        }
+    }
+    [day(s), short_day(s)].into_iter().flatten().find_map(|day| {
+        let month = month_name(&s[day..])?;
+        Some(day + month + year(&s[day + month..])?)
+    })
+}
+
+// `(?:19|20)\d{2}`
+fn year(s: &str) -> Option<usize> {
+    number_in(s, 4, 1900..=2099)
+}
+
+// `0[1-9]|1[0-2]`
+fn month(s: &str) -> Option<usize> {
+    number_in(s, 2, 1..=12)
+}
+
+// `0[1-9]|[12]\d|3[01]`
+fn day(s: &str) -> Option<usize> {
+    number_in(s, 2, 1..=31)
+}
+
+// `[1-9]`
+fn short_day(s: &str) -> Option<usize> {
+    number_in(s, 1, 1..=9)
+}
+
+fn month_name(s: &str) -> Option<usize> {
+    MONTH_NAMES
+        .iter()
+        .find(|name| strip_prefix_ignore_case(s, name).is_some())
+        .map(|name| name.len())
+}
+
+// `len` if `s` starts with exactly that many digits making a number in `range`
+fn number_in(s: &str, len: usize, range: std::ops::RangeInclusive<u32>) -> Option<usize> {
+    let digits = s.get(..len).filter(|digits| is_number(digits))?;
+    digits.parse().ok().filter(|n| range.contains(n)).map(|_| len)
+}
+
+fn is_number(s: &str) -> bool {
+    !s.is_empty() && s.bytes().all(|b| b.is_ascii_digit())
+}
+
+// `s` after the digits it starts with, if there are any
+fn skip_number(s: &str) -> Option<&str> {
+    let end = s.find(|c: char| !c.is_ascii_digit()).unwrap_or(s.len());
+    (end > 0).then(|| &s[end..])
+}
+
+// `s` without its trailing whitespace, if it has any
+fn strip_whitespace_end(s: &str) -> Option<&str> {
+    let trimmed = s.trim_end();
+    (trimmed.len() < s.len()).then_some(trimmed)
+}
+
+fn strip_prefix_ignore_case<'a>(s: &'a str, prefix: &str) -> Option<&'a str> {
+    let head = s.get(..prefix.len())?;
+    head.eq_ignore_ascii_case(prefix).then(|| &s[prefix.len()..])
+}
+
+fn strip_suffix_ignore_case<'a>(s: &'a str, suffix: &str) -> Option<&'a str> {
+    let start = s.len().checked_sub(suffix.len())?;
+    let tail = s.get(start..)?;
+    tail.eq_ignore_ascii_case(suffix).then(|| &s[..start])
+}
@@ -0,0 +1,79 @@
+use std::fmt;
+use std::sync::Arc;
+
+use crate::{DashifyOptions, STAGES};
+
+type Step = Box<dyn Fn(&str) -> String + Send + Sync>;
+
+/// A sequence of named string transformations, for adding project-specific steps, such as expanding
+/// abbreviations, between the standard ones.
+///
+/// The default pipeline holds `dashify`'s own stages, under the names `explain` reports: `replace special characters`,
+/// `split camelCase`, `split numbers`, `lowercase`, `collapse separators` and `trim separators`.
+/// Like those stages, a pipeline knows nothing about extensions, so pass it a stem.
+pub struct DashifyPipeline {
+    steps: Vec<(String, Step)>,
+}
+
+impl DashifyPipeline {
+    /// A pipeline with no steps.
+    pub fn new() -> Self {
+        Self { steps: Vec::new() }
+    }
+
+    /// The standard stages, configured by `options`.
+    pub fn from_options(options: &DashifyOptions) -> Self {
+        let options = Arc::new(options.clone());
+        let mut pipeline = Self::new();
+        for &(name, stage) in STAGES {
+            let options = Arc::clone(&options);
+            pipeline = pipeline.add_step(name, move |s| stage(s, &options));
+        }
+        pipeline
+    }
+
+    /// Adds a step at the end.
+    pub fn add_step(mut self, name: &str, step: impl Fn(&str) -> String + Send + Sync + 'static) -> Self {
+        self.steps.push((name.to_string(), Box::new(step)));
+        self
+    }
+
+    /// Adds a step right after the one called `existing`, or at the end if there is no such step.
+    pub fn with_step_after(
+        mut self,
+        existing: &str,
+        name: &str,
+        step: impl Fn(&str) -> String + Send + Sync + 'static,
+    ) -> Self {
+        let index = self
+            .steps
+            .iter()
+            .position(|(step_name, _)| step_name == existing)
+            .map_or(self.steps.len(), |i| i + 1);
+        self.steps.insert(index, (name.to_string(), Box::new(step)));
+        self
+    }
+
+    pub fn step_names(&self) -> impl Iterator<Item = &str> {
+        self.steps.iter().map(|(name, _)| name.as_str())
+    }
+
+    /// Runs every step in order, each on the output of the one before.
+    pub fn apply(&self, name: &str) -> String {
+        self.steps.iter().fold(name.to_string(), |result, (_, step)| step(&result))
+    }
+}
+
+impl Default for DashifyPipeline {
+    fn default() -> Self {
+        Self::from_options(&DashifyOptions::default())
+    }
+}
+
+impl fmt::Debug for DashifyPipeline {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("DashifyPipeline")
+            .field("steps", &self.step_names().collect::<Vec<_>>())
+            .finish()
+    }
+}
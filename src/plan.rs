@@ -0,0 +1,129 @@
+use std::collections::BTreeMap;
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+#[cfg(feature = "serde")]
+use serde::Serialize;
+
+use crate::{compile, is_resource_fork, is_skipped_as_non_ascii, DashifyOptions, ProcessingStats, RenameOperation};
+
+/// Two or more files that would end up with the same name, or a rename onto a file that already exists.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct RenameConflict {
+    pub target: PathBuf,
+    pub sources: Vec<PathBuf>,
+}
+
+/// Everything a run would do, computed without touching the filesystem.
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub struct RenamePlan {
+    /// Renames that can be applied, in processing order.
+    pub operations: Vec<RenameOperation>,
+    pub conflicts: Vec<RenameConflict>,
+    pub unchanged: Vec<PathBuf>,
+    pub stats: ProcessingStats,
+}
+
+impl RenamePlan {
+    pub fn for_files(files: &[PathBuf], options: &DashifyOptions) -> Self {
+        let mut plan = RenamePlan::default();
+        let mut targets: BTreeMap<PathBuf, Vec<PathBuf>> = BTreeMap::new();
+        let compiled = compile(options).ok();
+        for from in files {
+            let Some(file_name) = from.file_name() else { continue };
+            let file_name = file_name.to_string_lossy();
+            if options.skip_resource_forks && is_resource_fork(&file_name) {
+                continue;
+            }
+            // a name dashify fails on can't be renamed, so it stays as it is
+            let to = compiled.as_ref().and_then(|compiled| compiled.dashify_path(from).ok());
+            let to = to.unwrap_or_else(|| from.clone());
+            if to == *from {
+                if options.report_non_ascii_separately && is_skipped_as_non_ascii(&file_name, options) {
+                    plan.stats.unicode_skipped += 1;
+                }
+                plan.unchanged.push(from.clone());
+            } else {
+                targets.entry(to).or_default().push(from.clone());
+            }
+        }
+
+        for (to, sources) in targets {
+            let occupied = to.symlink_metadata().is_ok() && !sources.iter().any(|from| is_same_file(from, &to));
+            if sources.len() > 1 || occupied {
+                plan.conflicts.push(RenameConflict { target: to, sources });
+            } else {
+                plan.operations.extend(sources.into_iter().map(|from| RenameOperation::new(from, &to)));
+            }
+        }
+        plan.operations.sort_by(|a, b| a.from.cmp(&b.from));
+        plan.unchanged.sort();
+
+        let unchanged_ops = plan.unchanged.iter().map(|path| RenameOperation::new(path, path));
+        for op in plan.operations.iter().cloned().chain(unchanged_ops) {
+            plan.stats.record(&op);
+        }
+        plan
+    }
+
+    /// Every path the plan would rename, including those blocked by a conflict.
+    pub fn needs_renaming(&self) -> Vec<PathBuf> {
+        let mut paths: Vec<PathBuf> = self
+            .operations
+            .iter()
+            .map(|op| op.from.clone())
+            .chain(self.conflicts.iter().flat_map(|conflict| conflict.sources.iter().cloned()))
+            .collect();
+        paths.sort();
+        paths
+    }
+}
+
+pub fn compute_rename_plan(dir: &Path, recursive: bool, options: &DashifyOptions) -> io::Result<RenamePlan> {
+    let mut files = Vec::new();
+    collect_files(dir, recursive, &mut files)?;
+    Ok(RenamePlan::for_files(&files, options))
+}
+
+/// Groups `ops` by the directory of their source path, in path order, e.g. for a per-directory preview.
+pub fn group_renames_by_directory(ops: Vec<RenameOperation>) -> BTreeMap<PathBuf, Vec<RenameOperation>> {
+    let mut groups: BTreeMap<PathBuf, Vec<RenameOperation>> = BTreeMap::new();
+    for op in ops {
+        let dir = op.from.parent().map(Path::to_path_buf).unwrap_or_default();
+        groups.entry(dir).or_default().push(op);
+    }
+    groups
+}
+
+pub(crate) fn collect_files(dir: &Path, recursive: bool, files: &mut Vec<PathBuf>) -> io::Result<()> {
+    for entry in fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_file() {
+            files.push(path);
+        } else if recursive && path.is_dir() {
+            collect_files(&path, true, files)?;
+        }
+    }
+    Ok(())
+}
+
+// On case-insensitive filesystems the target of a case-only rename "exists" because it is the source.
+#[cfg(unix)]
+pub(crate) fn is_same_file(a: &Path, b: &Path) -> bool {
+    use std::os::unix::fs::MetadataExt;
+    match (fs::metadata(a), fs::metadata(b)) {
+        (Ok(a), Ok(b)) => a.dev() == b.dev() && a.ino() == b.ino(),
+        _ => false,
+    }
+}
+
+#[cfg(not(unix))]
+pub(crate) fn is_same_file(a: &Path, b: &Path) -> bool {
+    match (fs::canonicalize(a), fs::canonicalize(b)) {
+        (Ok(a), Ok(b)) => a == b,
+        _ => false,
+    }
+}
@@ -0,0 +1,90 @@
+use std::env;
+
+use crate::RenameOperation;
+
+const RESET: &str = "\x1b[0m";
+const REMOVED: &str = "\x1b[31;9m";
+const ADDED: &str = "\x1b[32m";
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Change {
+    Same,
+    Removed,
+    Added,
+}
+
+/// Renders the rename as a single filename with removed characters in struck-through red and added ones in
+/// green, based on a character-level diff. Falls back to `from -> to` when `NO_COLOR` is set or `TERM` is
+/// `dumb`.
+pub fn preview_rename_colored(op: &RenameOperation) -> String {
+    if !use_color() {
+        return format!("{} -> {}", op.from.display(), op.to.display());
+    }
+    let from = op.from.file_name().map(|name| name.to_string_lossy()).unwrap_or_default();
+    let to = op.to.file_name().map(|name| name.to_string_lossy()).unwrap_or_default();
+    let dir = match op.from.parent() {
+        Some(parent) if !parent.as_os_str().is_empty() => format!("{}/", parent.display()),
+        _ => String::new(),
+    };
+
+    let mut preview = dir;
+    let mut current = Change::Same;
+    for (change, c) in diff_chars(&from, &to) {
+        if change != current {
+            // reset first, so going straight from removed to added drops the strikethrough
+            if current != Change::Same {
+                preview.push_str(RESET);
+            }
+            match change {
+                Change::Same => {}
+                Change::Removed => preview.push_str(REMOVED),
+                Change::Added => preview.push_str(ADDED),
+            }
+            current = change;
+        }
+        preview.push(c);
+    }
+    if current != Change::Same {
+        preview.push_str(RESET);
+    }
+    preview
+}
+
+fn use_color() -> bool {
+    env::var_os("NO_COLOR").is_none_or(|value| value.is_empty()) && env::var("TERM").map_or(true, |term| term != "dumb")
+}
+
+// Longest common subsequence, walked from the front so removals come before additions at each change.
+fn diff_chars(from: &str, to: &str) -> Vec<(Change, char)> {
+    let a: Vec<char> = from.chars().collect();
+    let b: Vec<char> = to.chars().collect();
+    let mut lcs = vec![vec![0usize; b.len() + 1]; a.len() + 1];
+    for i in (0..a.len()).rev() {
+        for j in (0..b.len()).rev() {
+            lcs[i][j] = if a[i] == b[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut diff = Vec::with_capacity(a.len().max(b.len()));
+    let (mut i, mut j) = (0, 0);
+    while i < a.len() && j < b.len() {
+        if a[i] == b[j] {
+            diff.push((Change::Same, a[i]));
+            i += 1;
+            j += 1;
+        } else if lcs[i + 1][j] >= lcs[i][j + 1] {
+            diff.push((Change::Removed, a[i]));
+            i += 1;
+        } else {
+            diff.push((Change::Added, b[j]));
+            j += 1;
+        }
+    }
+    diff.extend(a[i..].iter().map(|&c| (Change::Removed, c)));
+    diff.extend(b[j..].iter().map(|&c| (Change::Added, c)));
+    diff
+}
@@ -0,0 +1,344 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use filetime::FileTime;
+use rayon::prelude::*;
+
+use crate::plan::is_same_file;
+use crate::{
+    compile, split_name_and_extension_with, CompiledOptions, DashifyError, DashifyOptions, RenameOperation, RenamePlan,
+};
+
+/// What `rename_file_with_conflict_strategy` does when the dashified name is already taken.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum CollisionStrategy {
+    /// Replace the existing file.
+    Overwrite,
+    /// Leave the file under its current name.
+    Skip,
+    /// Use the first free name of `name-2.ext` through `name-999.ext`.
+    AppendCounter,
+    /// Fail with `DashifyError::CollisionDetected`.
+    #[default]
+    Error,
+}
+
+impl FromStr for CollisionStrategy {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "overwrite" => Ok(CollisionStrategy::Overwrite),
+            "skip" => Ok(CollisionStrategy::Skip),
+            "number" | "append-counter" => Ok(CollisionStrategy::AppendCounter),
+            "error" => Ok(CollisionStrategy::Error),
+            _ => Err(format!("expected skip, overwrite, number or error, got '{s}'")),
+        }
+    }
+}
+
+/// Dashifies the name of the file at `path` and renames it without overwriting anything.
+///
+/// Returns the new path, or `None` when the name is already dashified.
+pub fn rename_in_place(path: &Path, options: &DashifyOptions) -> Result<Option<PathBuf>, DashifyError> {
+    let plan = RenamePlan::for_files(&[path.to_path_buf()], options);
+    if let Some(conflict) = plan.conflicts.first() {
+        let target = conflict.target.display().to_string();
+        return Err(DashifyError::CollisionDetected {
+            first: path.display().to_string(),
+            second: target.clone(),
+            target,
+        });
+    }
+    let Some(op) = plan.operations.first() else {
+        return Ok(None);
+    };
+    rename_noreplace_keeping_times(&op.from, &op.to, options)?;
+    Ok(Some(op.to.clone()))
+}
+
+/// Dashifies the name of the file at `path` and renames it, resolving a clash with an existing file according to
+/// `strategy`. Returns the file's final path, which is `path` itself when nothing was renamed.
+pub fn rename_file_with_conflict_strategy(
+    path: &Path,
+    strategy: CollisionStrategy,
+    options: &DashifyOptions,
+) -> Result<PathBuf, DashifyError> {
+    compile(options)?.rename_file_with_conflict_strategy(path, strategy)
+}
+
+pub(crate) fn rename_compiled_with_conflict_strategy(
+    path: &Path,
+    strategy: CollisionStrategy,
+    options: &CompiledOptions<'_>,
+) -> Result<PathBuf, DashifyError> {
+    let Some(file_name) = path.file_name() else {
+        return Ok(path.to_path_buf());
+    };
+    let target = path.with_file_name(options.dashify_os_str(file_name)?);
+    if target == path {
+        return Ok(target);
+    }
+    match resolve_target(path, target, strategy, options)? {
+        Target::Free(target) => {
+            rename_noreplace_keeping_times(path, &target, options)?;
+            Ok(target)
+        }
+        Target::Taken(target) => {
+            RenameOperation::new(path, &target).apply(options)?;
+            Ok(target)
+        }
+        Target::Skip => Ok(path.to_path_buf()),
+    }
+}
+
+/// Copies the file at `path` into `dir` under its dashified name, resolving a clash with an existing file
+/// according to `strategy`; `path` itself is left untouched. Returns the copy's path, or `path` when skipped.
+pub fn copy_file_with_conflict_strategy(
+    path: &Path,
+    dir: &Path,
+    strategy: CollisionStrategy,
+    options: &DashifyOptions,
+) -> Result<PathBuf, DashifyError> {
+    compile(options)?.copy_file_with_conflict_strategy(path, dir, strategy)
+}
+
+pub(crate) fn copy_compiled_with_conflict_strategy(
+    path: &Path,
+    dir: &Path,
+    strategy: CollisionStrategy,
+    options: &CompiledOptions<'_>,
+) -> Result<PathBuf, DashifyError> {
+    let file_name = path.file_name().ok_or_else(|| DashifyError::NoFilename {
+        path: path.to_path_buf(),
+    })?;
+    let target = dir.join(options.dashify_os_str(file_name)?);
+    // copying a file onto itself would truncate it
+    if is_same_file(path, &target) {
+        return Ok(target);
+    }
+    let target = match resolve_target(path, target, strategy, options)? {
+        Target::Free(target) | Target::Taken(target) => target,
+        Target::Skip => return Ok(path.to_path_buf()),
+    };
+    let io_error = |path: &Path| {
+        let path = path.to_path_buf();
+        move |source| DashifyError::Io { path, source }
+    };
+    fs::copy(path, &target).map_err(io_error(path))?;
+    if options.preserve_timestamps {
+        let metadata = fs::metadata(path).map_err(io_error(path))?;
+        let atime = FileTime::from_last_access_time(&metadata);
+        let mtime = FileTime::from_last_modification_time(&metadata);
+        filetime::set_file_times(&target, atime, mtime).map_err(io_error(&target))?;
+    }
+    Ok(target)
+}
+
+// Where a file should go, given the `target` it would like
+enum Target {
+    Free(PathBuf),
+    /// Already taken, and the strategy says to replace what's there.
+    Taken(PathBuf),
+    Skip,
+}
+
+fn resolve_target(
+    source: &Path,
+    target: PathBuf,
+    strategy: CollisionStrategy,
+    options: &DashifyOptions,
+) -> Result<Target, DashifyError> {
+    if target.symlink_metadata().is_err() || is_same_file(source, &target) {
+        return Ok(Target::Free(target));
+    }
+    let collision = |target: &Path| DashifyError::CollisionDetected {
+        first: source.display().to_string(),
+        second: target.display().to_string(),
+        target: target.display().to_string(),
+    };
+    match strategy {
+        CollisionStrategy::Overwrite => Ok(Target::Taken(target)),
+        CollisionStrategy::Skip => Ok(Target::Skip),
+        CollisionStrategy::Error => Err(collision(&target)),
+        CollisionStrategy::AppendCounter => {
+            let name = target.file_name().unwrap_or_default().to_string_lossy();
+            let (stem, extension) = split_name_and_extension_with(&name, &options.compound_extensions);
+            (2..=999)
+                .map(|n| match extension {
+                    Some(extension) => target.with_file_name(format!("{stem}-{n}.{extension}")),
+                    None => target.with_file_name(format!("{stem}-{n}")),
+                })
+                .find(|candidate| candidate.symlink_metadata().is_err())
+                .map(Target::Free)
+                .ok_or_else(|| collision(&target))
+        }
+    }
+}
+
+// safe_rename, restoring the access and modification times afterwards if the options ask for it
+pub(crate) fn rename_noreplace_keeping_times(
+    from: &Path,
+    to: &Path,
+    options: &DashifyOptions,
+) -> Result<(), DashifyError> {
+    let io_error = |path: &Path| {
+        let path = path.to_path_buf();
+        move |source| DashifyError::Io { path, source }
+    };
+    let times = if options.preserve_timestamps {
+        let metadata = fs::metadata(from).map_err(io_error(from))?;
+        Some((
+            FileTime::from_last_access_time(&metadata),
+            FileTime::from_last_modification_time(&metadata),
+        ))
+    } else {
+        None
+    };
+    safe_rename(from, to)?;
+    if let Some((atime, mtime)) = times {
+        filetime::set_file_times(to, atime, mtime).map_err(io_error(to))?;
+    }
+    Ok(())
+}
+
+/// Renames `from` to `to`, refusing to overwrite an existing `to`.
+///
+/// On Linux this is done atomically with `renameat2(RENAME_NOREPLACE)`. Elsewhere, or on filesystems
+/// that don't support it, it falls back to checking for `to` before renaming, which can race.
+pub fn safe_rename(from: &Path, to: &Path) -> Result<(), DashifyError> {
+    let io_error = |source| DashifyError::Io {
+        path: from.to_path_buf(),
+        source,
+    };
+    // a case-only rename on a case-insensitive filesystem: `to` exists because it is `from`
+    if is_same_file(from, to) {
+        return fs::rename(from, to).map_err(io_error);
+    }
+
+    #[cfg(target_os = "linux")]
+    match rename_noreplace(from, to) {
+        Ok(()) => return Ok(()),
+        Err(e) if matches!(e.raw_os_error(), Some(libc::EINVAL) | Some(libc::ENOSYS)) => {}
+        Err(e) => return Err(io_error(e)),
+    }
+
+    if to.symlink_metadata().is_ok() {
+        return Err(io_error(io::Error::new(
+            io::ErrorKind::AlreadyExists,
+            format!("'{}' already exists", to.display()),
+        )));
+    }
+    fs::rename(from, to).map_err(io_error)
+}
+
+#[cfg(target_os = "linux")]
+fn rename_noreplace(from: &Path, to: &Path) -> io::Result<()> {
+    use std::ffi::CString;
+    use std::os::unix::ffi::OsStrExt;
+
+    let c_path = |path: &Path| {
+        CString::new(path.as_os_str().as_bytes()).map_err(|e| io::Error::new(io::ErrorKind::InvalidInput, e))
+    };
+    let (from, to) = (c_path(from)?, c_path(to)?);
+    // SAFETY: both paths are valid NUL-terminated strings that outlive the call.
+    let result = unsafe {
+        libc::renameat2(
+            libc::AT_FDCWD,
+            from.as_ptr(),
+            libc::AT_FDCWD,
+            to.as_ptr(),
+            libc::RENAME_NOREPLACE,
+        )
+    };
+    if result == 0 {
+        Ok(())
+    } else {
+        Err(io::Error::last_os_error())
+    }
+}
+
+/// Renames every file in `paths` or none of them.
+///
+/// Targets are checked for conflicts up front, then files are moved to temporary names and from there to
+/// their final names, so renames within the set can't clobber each other. On failure the completed steps
+/// are undone as far as possible without overwriting anything; if some can't be, e.g. because a file's original
+/// name has been taken since, the error is `IncompleteRollback`. Returns the final path of every input, in order.
+pub fn dashify_multiple_files_in_transaction(
+    paths: &[PathBuf],
+    options: &DashifyOptions,
+) -> Result<Vec<PathBuf>, DashifyError> {
+    let plan = RenamePlan::for_files(paths, options);
+    if let Some(conflict) = plan.conflicts.first() {
+        let target = conflict.target.display().to_string();
+        let first = conflict.sources[0].display().to_string();
+        let second = conflict.sources.get(1).map_or_else(|| target.clone(), |path| path.display().to_string());
+        return Err(DashifyError::CollisionDetected { first, second, target });
+    }
+
+    let pid = std::process::id();
+    let temps: Vec<PathBuf> = plan
+        .operations
+        .iter()
+        .enumerate()
+        .map(|(i, op)| op.from.with_file_name(format!(".dashify-{pid}-{i}.tmp")))
+        .collect();
+
+    for (done, (op, temp)) in plan.operations.iter().zip(&temps).enumerate() {
+        if let Err(e) = safe_rename(&op.from, temp) {
+            let stranded = rollback(plan.operations[..done].iter().zip(&temps).map(|(op, temp)| (temp, &op.from)));
+            return Err(with_stranded(e, stranded));
+        }
+    }
+    for (done, (op, temp)) in plan.operations.iter().zip(&temps).enumerate() {
+        if let Err(e) = safe_rename(temp, &op.to) {
+            let mut stranded = rollback(plan.operations[..done].iter().zip(&temps).map(|(op, temp)| (&op.to, temp)));
+            stranded.extend(rollback(plan.operations.iter().zip(&temps).map(|(op, temp)| (temp, &op.from))));
+            return Err(with_stranded(e, stranded));
+        }
+    }
+
+    Ok(paths
+        .iter()
+        .map(|path| {
+            plan.operations
+                .iter()
+                .find(|op| op.from == *path)
+                .map_or_else(|| path.clone(), |op| op.to.clone())
+        })
+        .collect())
+}
+
+/// Applies `ops` on up to `jobs` threads (0 means one per CPU), returning each result in the order of `ops`. If
+/// the threads can't be started, the operations are applied one at a time instead.
+///
+/// The operations must not depend on each other, e.g. the conflict-free operations of a `RenamePlan`.
+pub fn apply_renames_parallel(
+    ops: &[RenameOperation],
+    jobs: usize,
+    options: &DashifyOptions,
+) -> Vec<Result<(), DashifyError>> {
+    match rayon::ThreadPoolBuilder::new().num_threads(jobs).build() {
+        Ok(pool) => pool.install(|| ops.par_iter().map(|op| op.apply(options)).collect()),
+        Err(_) => ops.iter().map(|op| op.apply(options)).collect(),
+    }
+}
+
+// undoes `renames` without overwriting anything, returning the paths of the files that couldn't be moved back,
+// including those whose original name has been taken in the meantime
+fn rollback<'a>(renames: impl Iterator<Item = (&'a PathBuf, &'a PathBuf)>) -> Vec<PathBuf> {
+    renames.filter(|(from, to)| safe_rename(from, to).is_err()).map(|(from, _)| from.clone()).collect()
+}
+
+fn with_stranded(error: DashifyError, stranded: Vec<PathBuf>) -> DashifyError {
+    if stranded.is_empty() {
+        error
+    } else {
+        DashifyError::IncompleteRollback {
+            error: Box::new(error),
+            stranded,
+        }
+    }
+}
@@ -0,0 +1,104 @@
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::{ProcessingStats, RenameOperation};
+
+const STYLE: &str = "
+body { font-family: -apple-system, Helvetica, Arial, sans-serif; margin: 2em; color: #222; }
+h1 { font-size: 1.5em; }
+h2 { font-size: 1.2em; margin-top: 2em; }
+table { border-collapse: collapse; margin-top: 0.5em; }
+th, td { border: 1px solid #ccc; padding: 4px 10px; text-align: left; font-family: monospace; }
+th { background: #eee; font-family: inherit; }
+tr.changed td { background: #e6f4ea; }
+tr.unchanged td { color: #777; }
+tr.error td { background: #fce8e6; }
+.timestamp { color: #777; font-size: 0.9em; }
+";
+
+pub fn generate_html_report(ops: &[RenameOperation], stats: &ProcessingStats) -> String {
+    let mut html = String::new();
+    html.push_str("<!DOCTYPE html>\n<html lang=\"en\">\n<head>\n<meta charset=\"utf-8\">\n");
+    html.push_str("<title>dashify report</title>\n");
+    html.push_str(&format!("<style>{STYLE}</style>\n"));
+    html.push_str("</head>\n<body>\n<h1>dashify report</h1>\n");
+    html.push_str(&format!("<p class=\"timestamp\">Generated {}</p>\n", format_timestamp(SystemTime::now())));
+
+    html.push_str("<h2>Summary</h2>\n<table>\n");
+    for (label, value) in [
+        ("Files processed", stats.processed.to_string()),
+        ("Renamed", stats.renamed.to_string()),
+        ("Unchanged", stats.unchanged.to_string()),
+        ("Bytes saved", stats.bytes_saved.to_string()),
+        ("Errors", stats.errors.len().to_string()),
+    ] {
+        html.push_str(&format!("<tr><th>{label}</th><td>{value}</td></tr>\n"));
+    }
+    if stats.unicode_skipped > 0 {
+        html.push_str(&format!("<tr><th>Skipped (non-ASCII)</th><td>{}</td></tr>\n", stats.unicode_skipped));
+    }
+    html.push_str("</table>\n");
+
+    html.push_str("<h2>Renames</h2>\n<table>\n<tr><th>From</th><th>To</th></tr>\n");
+    for op in ops {
+        let class = if op.is_change() { "changed" } else { "unchanged" };
+        html.push_str(&format!(
+            "<tr class=\"{class}\"><td>{}</td><td>{}</td></tr>\n",
+            escape_html(&op.from.to_string_lossy()),
+            escape_html(&op.to.to_string_lossy())
+        ));
+    }
+    html.push_str("</table>\n");
+
+    if !stats.errors.is_empty() {
+        html.push_str("<h2>Errors</h2>\n<table>\n<tr><th>Path</th><th>Error</th></tr>\n");
+        for (path, error) in &stats.errors {
+            html.push_str(&format!(
+                "<tr class=\"error\"><td>{}</td><td>{}</td></tr>\n",
+                escape_html(&path.to_string_lossy()),
+                escape_html(error)
+            ));
+        }
+        html.push_str("</table>\n");
+    }
+
+    html.push_str("</body>\n</html>\n");
+    html
+}
+
+fn escape_html(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+fn format_timestamp(time: SystemTime) -> String {
+    let secs = time.duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0);
+    let (days, rem) = (secs / 86_400, secs % 86_400);
+
+    // civil-from-days, see http://howardhinnant.github.io/date_algorithms.html
+    let z = days as i64 + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1_460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + i64::from(month <= 2);
+
+    format!(
+        "{year:04}-{month:02}-{day:02} {:02}:{:02}:{:02} UTC",
+        rem / 3_600,
+        rem % 3_600 / 60,
+        rem % 60
+    )
+}
@@ -0,0 +1,93 @@
+use std::fmt;
+
+use crate::RenameOperation;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub line: usize,
+    pub message: String,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Renders `ops` as a POSIX shell script of `mv -- 'from' 'to'` lines.
+pub fn generate_mv_script(ops: &[RenameOperation]) -> String {
+    let mut script = String::from("#!/bin/sh\nset -e\n");
+    for op in ops.iter().filter(|op| op.is_change()) {
+        script.push_str(&format!(
+            "mv -- {} {}\n",
+            shell_quote(&op.from.to_string_lossy()),
+            shell_quote(&op.to.to_string_lossy())
+        ));
+    }
+    script
+}
+
+/// Parses a script produced by `generate_mv_script` back into rename operations.
+pub fn parse_rename_script(script: &str) -> Result<Vec<RenameOperation>, ParseError> {
+    let mut ops = Vec::new();
+    for (index, line) in script.lines().enumerate() {
+        let line_number = index + 1;
+        let error = |message: String| ParseError {
+            line: line_number,
+            message,
+        };
+        let line = line.trim();
+        if line.is_empty() || line.starts_with('#') || line.starts_with("set ") {
+            continue;
+        }
+        let words = split_shell_words(line).map_err(error)?;
+        let args = match words.split_first() {
+            Some((command, args)) if command == "mv" => args,
+            _ => return Err(error(format!("expected an mv command, found: {line}"))),
+        };
+        let args = args.strip_prefix(&["--".to_string()]).unwrap_or(args);
+        match args {
+            [from, to] => ops.push(RenameOperation::new(from, to)),
+            _ => return Err(error(format!("expected 2 paths after mv, found {}", args.len()))),
+        }
+    }
+    Ok(ops)
+}
+
+fn shell_quote(s: &str) -> String {
+    format!("'{}'", s.replace('\'', r"'\''"))
+}
+
+fn split_shell_words(line: &str) -> Result<Vec<String>, String> {
+    let mut words = Vec::new();
+    let mut word: Option<String> = None;
+    let mut chars = line.chars();
+    while let Some(c) = chars.next() {
+        match c {
+            '\'' => {
+                let word = word.get_or_insert_with(String::new);
+                loop {
+                    match chars.next() {
+                        Some('\'') => break,
+                        Some(c) => word.push(c),
+                        None => return Err("unterminated single quote".to_string()),
+                    }
+                }
+            }
+            '\\' => match chars.next() {
+                Some(c) => word.get_or_insert_with(String::new).push(c),
+                None => return Err("trailing backslash".to_string()),
+            },
+            c if c.is_whitespace() => {
+                if let Some(word) = word.take() {
+                    words.push(word);
+                }
+            }
+            c => word.get_or_insert_with(String::new).push(c),
+        }
+    }
+    words.extend(word);
+    Ok(words)
+}
@@ -0,0 +1,116 @@
+use std::fmt;
+use std::fs;
+#[cfg(feature = "serde")]
+use std::io::{self, BufRead, BufReader, BufWriter, Write};
+#[cfg(feature = "serde")]
+use std::path::Path;
+use std::path::PathBuf;
+
+#[cfg(feature = "serde")]
+use serde::Deserialize;
+
+#[cfg(feature = "serde")]
+use crate::LogOp;
+use crate::{DashifyError, RenameOperation};
+
+/// The renames performed in one run, in order, so they can be saved and undone later.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RenameSession {
+    pub renames: Vec<RenameOperation>,
+}
+
+/// Returned by `RenameSession::rollback` when some renames could not be undone; the others still were.
+#[derive(Debug)]
+pub struct RollbackError {
+    pub restored: Vec<PathBuf>,
+    pub failures: Vec<DashifyError>,
+}
+
+impl fmt::Display for RollbackError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{} rename(s) could not be undone", self.failures.len())?;
+        for failure in &self.failures {
+            write!(f, "\n  {failure}")?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for RollbackError {}
+
+impl RenameSession {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds `op` to the session if it actually renamed something.
+    pub fn record(&mut self, op: &RenameOperation) {
+        if op.is_change() {
+            self.renames.push(op.clone());
+        }
+    }
+
+    /// Undoes the renames, newest first, returning the restored paths.
+    ///
+    /// Renames whose new name no longer exists, or whose old name has been taken again, are skipped.
+    pub fn rollback(&self) -> Result<Vec<PathBuf>, RollbackError> {
+        let mut restored = Vec::new();
+        let mut failures = Vec::new();
+        for op in self.renames.iter().rev() {
+            if op.to.symlink_metadata().is_err() || op.from.symlink_metadata().is_ok() {
+                continue;
+            }
+            match fs::rename(&op.to, &op.from) {
+                Ok(()) => restored.push(op.from.clone()),
+                Err(source) => failures.push(DashifyError::Io {
+                    path: op.to.clone(),
+                    source,
+                }),
+            }
+        }
+        if failures.is_empty() {
+            Ok(restored)
+        } else {
+            Err(RollbackError { restored, failures })
+        }
+    }
+
+    /// Writes the session as JSON Lines, one `{"from": ..., "to": ...}` object per rename. Needs the `serde` feature.
+    #[cfg(feature = "serde")]
+    pub fn save(&self, path: &Path) -> io::Result<()> {
+        let mut writer = BufWriter::new(fs::File::create(path)?);
+        for op in &self.renames {
+            serde_json::to_writer(&mut writer, op)?;
+            writeln!(writer)?;
+        }
+        writer.flush()
+    }
+
+    /// Reads a session written by `save`, or the renames from an audit log written by `JsonLinesLogger`.
+    #[cfg(feature = "serde")]
+    pub fn load(path: &Path) -> io::Result<Self> {
+        let mut renames = Vec::new();
+        for (index, line) in BufReader::new(fs::File::open(path)?).lines().enumerate() {
+            let line = line?;
+            if line.trim().is_empty() {
+                continue;
+            }
+            let line: SessionLine = serde_json::from_str(&line).map_err(|e| {
+                io::Error::new(io::ErrorKind::InvalidData, format!("{}:{}: {e}", path.display(), index + 1))
+            })?;
+            if let (None | Some(LogOp::Rename), Some(to)) = (line.op, line.to) {
+                renames.push(RenameOperation::new(line.from, to));
+            }
+        }
+        Ok(Self { renames })
+    }
+}
+
+// A line of a saved session, or of an audit log, where only renames count
+#[cfg(feature = "serde")]
+#[derive(Deserialize)]
+struct SessionLine {
+    op: Option<LogOp>,
+    from: PathBuf,
+    to: Option<PathBuf>,
+}
@@ -0,0 +1,49 @@
+use crate::convention::capitalize;
+use crate::{process_name, DashifyOptions};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SqlDialect {
+    /// `lower_snake_case`, at most 63 characters.
+    Postgres,
+    /// `lower_snake_case`, at most 64 characters.
+    MySQL,
+    /// `PascalCase`, at most 128 characters.
+    SqlServer,
+}
+
+impl SqlDialect {
+    fn max_identifier_len(self) -> usize {
+        match self {
+            SqlDialect::Postgres => 63,
+            SqlDialect::MySQL => 64,
+            SqlDialect::SqlServer => 128,
+        }
+    }
+}
+
+/// Converts `name` to an unquoted identifier in the dialect's usual case, truncated to its length limit.
+///
+/// Everything but letters, digits and underscores is dropped, and a leading digit gets a `_` prefix.
+pub fn dashify_sql_identifier(name: &str, dialect: SqlDialect) -> String {
+    let options = DashifyOptions {
+        force_dash: true,
+        ..DashifyOptions::default()
+    };
+    let words: Vec<String> = process_name(name, &options)
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_string)
+        .collect();
+    let mut identifier = match dialect {
+        SqlDialect::Postgres | SqlDialect::MySQL => words.join("_"),
+        SqlDialect::SqlServer => words.iter().map(|word| capitalize(word)).collect(),
+    };
+    if identifier.is_empty() || identifier.starts_with(|c: char| c.is_ascii_digit()) {
+        identifier.insert(0, '_');
+    }
+    identifier.truncate(dialect.max_identifier_len());
+    while identifier.len() > 1 && identifier.ends_with('_') {
+        identifier.pop();
+    }
+    identifier
+}
@@ -0,0 +1,78 @@
+//! Assertions for checking that a dashified name is valid for a particular use.
+//!
+//! Each helper panics with a descriptive message when the string does not match.
+
+use crate::{dashify, patterns, DashifyOptions};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Target {
+    Slug,
+    K8sName,
+    EnvVar,
+}
+
+impl Target {
+    fn pattern(self) -> &'static str {
+        match self {
+            Target::Slug => "^[a-z0-9]+(-[a-z0-9]+)*$",
+            Target::K8sName => "^[a-z0-9]([-a-z0-9]*[a-z0-9])?$",
+            Target::EnvVar => "^[A-Z_][A-Z0-9_]*$",
+        }
+    }
+
+    fn matches(self, s: &str) -> bool {
+        match self {
+            Target::Slug => patterns::is_slug(s),
+            Target::K8sName => patterns::is_k8s_name(s),
+            Target::EnvVar => patterns::is_env_var(s),
+        }
+    }
+
+    fn max_length(self) -> Option<usize> {
+        match self {
+            Target::K8sName => Some(63),
+            _ => None,
+        }
+    }
+}
+
+// why `s` isn't a valid `target`, if it isn't
+fn problem_with(s: &str, target: Target) -> Option<String> {
+    if !target.matches(s) {
+        return Some(format!("does not match {}", target.pattern()));
+    }
+    match target.max_length() {
+        Some(max) if s.len() > max => Some(format!("{} characters exceeds the limit of {max}", s.len())),
+        _ => None,
+    }
+}
+
+pub fn assert_valid_for(s: &str, target: Target) {
+    if let Some(problem) = problem_with(s, target) {
+        panic!("{s:?} is not a valid {target:?}: {problem}");
+    }
+}
+
+/// Dashifies `filename` and checks the result like `assert_valid_for`; the message names the input and the
+/// options as well as the output.
+pub fn assert_dashified_is_valid_for(filename: &str, options: &DashifyOptions, target: Target) {
+    let dashified = match dashify(filename, options) {
+        Ok(dashified) => dashified,
+        Err(e) => panic!("dashifying {filename:?} failed: {e}\noptions: {options:?}"),
+    };
+    if let Some(problem) = problem_with(&dashified, target) {
+        panic!("{filename:?} dashified to {dashified:?}, not a valid {target:?}: {problem}\noptions: {options:?}");
+    }
+}
+
+pub fn assert_valid_slug(s: &str) {
+    assert_valid_for(s, Target::Slug);
+}
+
+pub fn assert_valid_k8s_name(s: &str) {
+    assert_valid_for(s, Target::K8sName);
+}
+
+pub fn assert_valid_env_var(s: &str) {
+    assert_valid_for(s, Target::EnvVar);
+}
@@ -0,0 +1,56 @@
+//! The individual steps of `dashify`, for composing your own pipeline. Each one does only its own job: none of
+//! them lowercases, and none knows about extensions, so pass them a stem.
+
+use crate::{collapse_mixed_separators_with, split_camel_case_with, split_numbers_with, DashifyOptions};
+
+/// Inserts `-` where a camelCase word ends and where an acronym ends before the next word. A lowercase word has to
+/// be at least two letters long to be split off, so one-letter prefixes stay attached (see
+/// `DashifyOptions::abbreviation_min_length`). Only ASCII letters count; the case of every letter is kept.
+///
+/// ```
+/// use dashify::transform::split_camel_case;
+///
+/// assert_eq!(split_camel_case("myFileName"), "my-File-Name");
+/// assert_eq!(split_camel_case("XMLParser"), "XML-Parser");
+/// assert_eq!(split_camel_case("parseHTML"), "parse-HTML");
+/// assert_eq!(split_camel_case("iPhone"), "iPhone");
+/// assert_eq!(split_camel_case("already-split"), "already-split");
+/// ```
+pub fn split_camel_case(s: &str) -> String {
+    split_camel_case_with(s, &DashifyOptions::default())
+}
+
+/// Inserts `-` at every boundary between an ASCII letter and an ASCII digit, in either direction, except within
+/// ordinals such as `1st` and `22nd`, hex literals such as `0xFF`, UUIDs and dates such as `2024Jan15`. Other
+/// characters are never a boundary, so existing separators aren't doubled.
+///
+/// ```
+/// use dashify::transform::split_numbers;
+///
+/// assert_eq!(split_numbers("file2"), "file-2");
+/// assert_eq!(split_numbers("2024report"), "2024-report");
+/// assert_eq!(split_numbers("v1.2"), "v-1.2");
+/// assert_eq!(split_numbers("page-10"), "page-10");
+/// assert_eq!(split_numbers("3rd-Party"), "3rd-Party");
+/// assert_eq!(split_numbers("0xDEAD-patch"), "0xDEAD-patch");
+/// ```
+pub fn split_numbers(s: &str) -> String {
+    split_numbers_with(s, &DashifyOptions::default())
+}
+
+/// Replaces every run of `-` and `_` with a single separator: `-` if the run contains a dash, otherwise `_`.
+///
+/// Runs are treated the same wherever they occur, and nothing is trimmed: `dashify` trims leading and trailing
+/// separators in a later step.
+///
+/// ```
+/// use dashify::transform::collapse_mixed_separators;
+///
+/// assert_eq!(collapse_mixed_separators("a--b"), "a-b");
+/// assert_eq!(collapse_mixed_separators("a_-_b"), "a-b");
+/// assert_eq!(collapse_mixed_separators("snake__case"), "snake_case");
+/// assert_eq!(collapse_mixed_separators("_-hello"), "-hello");
+/// ```
+pub fn collapse_mixed_separators(s: &str) -> String {
+    collapse_mixed_separators_with(s, '-')
+}
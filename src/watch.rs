@@ -0,0 +1,82 @@
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, RecvTimeoutError};
+use std::time::{Duration, Instant};
+
+use notify::event::{CreateKind, ModifyKind, RenameMode};
+use notify::{EventKind, RecursiveMode, Watcher};
+
+use crate::{compile, is_resource_fork, DashifyOptions};
+
+/// How long a file has to go without events before it counts as fully written.
+const SETTLE_TIME: Duration = Duration::from_millis(200);
+const POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Watches `dirs` for files being created or moved in, and calls `on_file` with each one whose name `dashify`
+/// would change. A file is only passed on once it has gone 200 ms without events, so partial writes aren't acted
+/// on. Errors the watcher reports along the way are passed to `on_error`, and watching goes on. Runs until `stop`
+/// is set.
+pub fn watch_for_new_files(
+    dirs: &[PathBuf],
+    recursive: bool,
+    options: &DashifyOptions,
+    stop: &AtomicBool,
+    mut on_file: impl FnMut(&Path),
+    mut on_error: impl FnMut(notify::Error),
+) -> notify::Result<()> {
+    let (tx, rx) = mpsc::channel();
+    let mut watcher = notify::recommended_watcher(tx)?;
+    let mode = if recursive { RecursiveMode::Recursive } else { RecursiveMode::NonRecursive };
+    for dir in dirs {
+        watcher.watch(dir, mode)?;
+    }
+
+    // with an invalid preserve pattern no name counts as dashified, as with `DashifyExt::is_dashified`
+    let compiled = compile(options).ok();
+    let mut pending: HashMap<PathBuf, Instant> = HashMap::new();
+    while !stop.load(Ordering::Relaxed) {
+        match rx.recv_timeout(POLL_INTERVAL) {
+            Ok(Ok(event)) => match event.kind {
+                EventKind::Create(CreateKind::File | CreateKind::Any)
+                | EventKind::Modify(ModifyKind::Name(RenameMode::To | RenameMode::Both | RenameMode::Any)) => {
+                    for path in event.paths {
+                        pending.insert(path, Instant::now());
+                    }
+                }
+                // still being written
+                EventKind::Modify(_) => {
+                    for path in event.paths {
+                        if let Some(seen) = pending.get_mut(&path) {
+                            *seen = Instant::now();
+                        }
+                    }
+                }
+                _ => {}
+            },
+            Ok(Err(e)) => on_error(e),
+            Err(RecvTimeoutError::Timeout) => {}
+            Err(RecvTimeoutError::Disconnected) => break,
+        }
+
+        let settled: Vec<PathBuf> = pending
+            .iter()
+            .filter(|(_, seen)| seen.elapsed() >= SETTLE_TIME)
+            .map(|(path, _)| path.clone())
+            .collect();
+        for path in settled {
+            pending.remove(&path);
+            let Some(file_name) = path.file_name() else { continue };
+            let file_name = file_name.to_string_lossy();
+            if options.skip_resource_forks && is_resource_fork(&file_name) {
+                continue;
+            }
+            // our own renames show up here too, already dashified
+            let dashified = compiled.as_ref().is_some_and(|compiled| compiled.is_path_dashified(&path));
+            if path.is_file() && !dashified {
+                on_file(&path);
+            }
+        }
+    }
+    Ok(())
+}
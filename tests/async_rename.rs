@@ -0,0 +1,80 @@
+#![cfg(feature = "async")]
+
+use std::fs;
+use std::path::Path;
+use std::sync::Arc;
+
+use dashify::{dashify_dir_async, DashifyError, DashifyOptions, RenameOperation};
+use futures_util::StreamExt;
+use tempfile::TempDir;
+
+fn dir_with(names: &[&str]) -> TempDir {
+    let dir = tempfile::tempdir().unwrap();
+    for name in names {
+        let path = dir.path().join(name);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, "").unwrap();
+    }
+    dir
+}
+
+async fn dashify_dir(dir: &Path, recursive: bool) -> Vec<Result<RenameOperation, DashifyError>> {
+    dashify_dir_async(dir, Arc::new(DashifyOptions::default()), recursive).collect().await
+}
+
+fn names(dir: &Path) -> Vec<String> {
+    let mut names: Vec<String> =
+        fs::read_dir(dir).unwrap().map(|entry| entry.unwrap().file_name().to_string_lossy().into_owned()).collect();
+    names.sort();
+    names
+}
+
+#[tokio::test]
+async fn files_are_renamed_and_each_rename_is_yielded() {
+    let dir = dir_with(&["My File.txt", "clean.txt"]);
+    let root = dir.path();
+    let mut ops: Vec<RenameOperation> = dashify_dir(root, false).await.into_iter().map(Result::unwrap).collect();
+    ops.sort_by(|a, b| a.from.cmp(&b.from));
+
+    assert_eq!(
+        ops,
+        [
+            RenameOperation::new(root.join("My File.txt"), root.join("my-file.txt")),
+            RenameOperation::new(root.join("clean.txt"), root.join("clean.txt")),
+        ]
+    );
+    assert_eq!(names(root), ["clean.txt", "my-file.txt"]);
+}
+
+#[tokio::test]
+async fn subdirectories_are_only_entered_when_recursive() {
+    let dir = dir_with(&["Top File.txt", "Sub Dir/Inner File.txt"]);
+    let root = dir.path();
+
+    assert_eq!(dashify_dir(root, false).await.len(), 1);
+    assert_eq!(names(&root.join("Sub Dir")), ["Inner File.txt"]);
+
+    let results = dashify_dir(root, true).await;
+    assert!(results.iter().all(Result::is_ok));
+    assert_eq!(names(root), ["Sub Dir", "top-file.txt"]);
+    assert_eq!(names(&root.join("Sub Dir")), ["inner-file.txt"]);
+}
+
+#[tokio::test]
+async fn taken_names_are_errors_and_nothing_is_overwritten() {
+    let dir = dir_with(&["My File.txt", "my-file.txt"]);
+    fs::write(dir.path().join("my-file.txt"), "keep me").unwrap();
+    let results = dashify_dir(dir.path(), false).await;
+
+    assert_eq!(results.len(), 2);
+    assert_eq!(results.iter().filter(|result| result.is_err()).count(), 1);
+    assert_eq!(names(dir.path()), ["My File.txt", "my-file.txt"]);
+    assert_eq!(fs::read_to_string(dir.path().join("my-file.txt")).unwrap(), "keep me");
+}
+
+#[tokio::test]
+async fn a_missing_directory_is_an_error_item() {
+    let dir = tempfile::tempdir().unwrap();
+    let results = dashify_dir(&dir.path().join("missing"), true).await;
+    assert!(matches!(results.as_slice(), [Err(DashifyError::Io { .. })]));
+}
@@ -0,0 +1,69 @@
+use dashify::{dashify, dashify_batch, dashify_batch_parallel, DashifyOptions};
+use proptest::prelude::*;
+
+const NAMES: &[&str] = &[
+    "My File.txt",
+    "myFileName.rs",
+    "XMLParser.java",
+    "HTTP2Request.go",
+    "already-clean.txt",
+    "snake_case_name.py",
+    "README",
+    "__init__.py",
+    "v1.2.3-release.tar.gz",
+    "Copy of Report (2).pdf",
+    "Café Menu.txt",
+    "---.txt",
+    ".hidden File",
+    "",
+    "file~.txt",
+    "3rdParty 0xFF Build.log",
+];
+
+fn dashify_or_keep(name: &str, options: &DashifyOptions) -> String {
+    dashify(name, options).unwrap_or_else(|_| name.to_string())
+}
+
+#[test]
+fn batch_matches_dashify_one_by_one() {
+    for options in [DashifyOptions::default(), DashifyOptions { force_dash: true, ..DashifyOptions::default() }] {
+        let (dashified, changed) = dashify_batch(NAMES, &options);
+        assert_eq!(dashified.len(), NAMES.len());
+        for ((name, dashified), changed) in NAMES.iter().zip(&dashified).zip(&changed) {
+            assert_eq!(*dashified, dashify_or_keep(name, &options), "name {name:?}");
+            assert_eq!(*changed, dashified != name, "name {name:?}");
+        }
+    }
+}
+
+#[test]
+fn single_names_match_dashify() {
+    let options = DashifyOptions::default();
+    for name in NAMES {
+        assert_eq!(dashify_batch(&[name], &options).0[0], dashify_or_keep(name, &options));
+    }
+}
+
+#[test]
+fn failures_come_back_unchanged() {
+    let (dashified, changed) = dashify_batch(&["---.txt", "My File.txt"], &DashifyOptions::default());
+    assert_eq!(dashified, ["---.txt", "my-file.txt"]);
+    assert_eq!(changed, [false, true]);
+}
+
+#[test]
+fn parallel_batch_matches_batch() {
+    let options = DashifyOptions::default();
+    assert_eq!(dashify_batch_parallel(NAMES, &options), dashify_batch(NAMES, &options));
+}
+
+proptest! {
+    #[test]
+    fn batch_matches_dashify(names in prop::collection::vec("[ -~]{0,32}", 0..16)) {
+        let options = DashifyOptions::default();
+        let names: Vec<&str> = names.iter().map(String::as_str).collect();
+        let (dashified, _) = dashify_batch(&names, &options);
+        let expected: Vec<String> = names.iter().map(|name| dashify_or_keep(name, &options)).collect();
+        prop_assert_eq!(dashified, expected);
+    }
+}
@@ -0,0 +1,106 @@
+use dashify::{dashify, dashify_cargo_feature, DashifyOptions};
+
+fn dashify_default(name: &str) -> String {
+    dashify(name, &DashifyOptions::default()).unwrap()
+}
+
+#[test]
+fn single_capital_before_a_word_starts_its_own_word() {
+    assert_eq!(dashify_default("AFile.txt"), "a-file.txt");
+    assert_eq!(dashify_default("ATest.txt"), "a-test.txt");
+}
+
+#[test]
+fn all_caps_name_is_left_alone() {
+    assert_eq!(dashify_default("AIO.txt"), "AIO.txt");
+}
+
+#[test]
+fn acronym_ends_before_the_capital_of_the_next_word() {
+    assert_eq!(dashify_default("ABCdef.txt"), "ab-cdef.txt");
+}
+
+#[test]
+fn pascal_case_words_count_their_capital() {
+    assert_eq!(dashify_default("MyFile.txt"), "my-file.txt");
+    assert_eq!(dashify_default("MyDocument.docx"), "my-document.docx");
+    assert_eq!(dashify_default("ToDo.txt"), "to-do.txt");
+    assert_eq!(dashify_default("OfThe.txt"), "of-the.txt");
+}
+
+#[test]
+fn one_letter_prefix_stays_attached() {
+    assert_eq!(dashify_default("iPhone.jpg"), "iphone.jpg");
+}
+
+#[test]
+fn cargo_feature_names_split_pascal_case() {
+    assert_eq!(dashify_cargo_feature("MyFeatureFlag"), "my-feature-flag");
+    assert_eq!(dashify_cargo_feature("2FASupport"), "f-2-fa-support");
+    assert_eq!(dashify_cargo_feature("JSON_Parsing"), "json-parsing");
+}
+
+#[test]
+fn acronym_at_the_end_is_split_off() {
+    assert_eq!(dashify_default("parseHTML.js"), "parse-html.js");
+    assert_eq!(dashify_default("getID.rs"), "get-id.rs");
+    assert_eq!(dashify_default("loadJSON.js"), "load-json.js");
+    assert_eq!(dashify_default("fetchURL.js"), "fetch-url.js");
+    assert_eq!(dashify_default("buildCSS.js"), "build-css.js");
+}
+
+fn dashify_with_threshold(name: &str, abbreviation_min_length: usize) -> String {
+    let options = DashifyOptions {
+        abbreviation_min_length,
+        ..DashifyOptions::default()
+    };
+    dashify(name, &options).unwrap()
+}
+
+#[test]
+fn threshold_one_splits_every_boundary() {
+    assert_eq!(dashify_with_threshold("iBurst", 1), "i-burst");
+    assert_eq!(dashify_with_threshold("iOSDevice", 1), "i-os-device");
+}
+
+#[test]
+fn threshold_two_keeps_one_letter_prefixes() {
+    assert_eq!(dashify_with_threshold("iBurst", 2), "iburst");
+    assert_eq!(dashify_with_threshold("iOSDevice", 2), "ios-device");
+    assert_eq!(dashify_with_threshold("myFile", 2), "my-file");
+}
+
+#[test]
+fn threshold_three_joins_two_letter_words() {
+    assert_eq!(dashify_with_threshold("myFile", 3), "myfile");
+    assert_eq!(dashify_with_threshold("ToDo", 3), "todo");
+    // the end of an acronym is a boundary whatever the threshold
+    assert_eq!(dashify_with_threshold("iOSDevice", 3), "ios-device");
+}
+
+fn dashify_with_acronyms(name: &str) -> String {
+    let options = DashifyOptions::builder().acronym("OAuth").acronym("GraphQL").acronym("MyQL").build().unwrap();
+    dashify(name, &options).unwrap()
+}
+
+#[test]
+fn dictionary_acronyms_are_not_split() {
+    assert_eq!(dashify_with_acronyms("OAuth2Flow.rs"), "oauth-2-flow.rs");
+    assert_eq!(dashify_with_acronyms("GraphQLServer.js"), "graphql-server.js");
+    assert_eq!(dashify_with_acronyms("MyQLReader.java"), "myql-reader.java");
+    assert_eq!(dashify_default("GraphQLServer.js"), "graph-ql-server.js");
+}
+
+#[test]
+fn dictionary_acronyms_are_found_inside_compound_names() {
+    assert_eq!(dashify_with_acronyms("MyOAuthClient.ts"), "my-oauth-client.ts");
+    assert_eq!(dashify_with_acronyms("UseGraphQL.ts"), "use-graphql.ts");
+    assert_eq!(dashify_with_acronyms("OAuthGraphQLBridge.ts"), "oauth-graphql-bridge.ts");
+}
+
+#[test]
+fn dictionary_acronyms_match_ignoring_case_only_at_word_boundaries() {
+    assert_eq!(dashify_with_acronyms("GRAPHQLServer.js"), "graphql-server.js");
+    assert_eq!(dashify_with_acronyms("photoauthor.txt"), "photoauthor.txt");
+    assert_eq!(dashify_with_acronyms("PDFReader.java"), "pdf-reader.java");
+}
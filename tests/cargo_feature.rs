@@ -0,0 +1,40 @@
+use dashify::dashify_cargo_feature;
+
+fn is_valid_feature(name: &str) -> bool {
+    name.starts_with(|c: char| c.is_ascii_lowercase())
+        && name.chars().all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-')
+}
+
+#[test]
+fn requested_examples() {
+    assert_eq!(dashify_cargo_feature("MyFeatureFlag"), "my-feature-flag");
+    assert_eq!(dashify_cargo_feature("2FASupport"), "f-2-fa-support");
+    assert_eq!(dashify_cargo_feature("JSON_Parsing"), "json-parsing");
+}
+
+#[test]
+fn underscores_and_spaces_become_dashes() {
+    assert_eq!(dashify_cargo_feature("serde_json"), "serde-json");
+    assert_eq!(dashify_cargo_feature("with tokio"), "with-tokio");
+    assert_eq!(dashify_cargo_feature("--weird--"), "weird");
+}
+
+#[test]
+fn feature_names_are_kept() {
+    assert_eq!(dashify_cargo_feature("async-std"), "async-std");
+    assert_eq!(dashify_cargo_feature("rt"), "rt");
+}
+
+#[test]
+fn leading_digits_get_a_prefix() {
+    assert_eq!(dashify_cargo_feature("3d"), "f-3-d");
+    assert_eq!(dashify_cargo_feature("HTTP2"), "http-2");
+}
+
+#[test]
+fn results_are_valid_feature_names() {
+    for name in ["MyFeatureFlag", "2FASupport", "JSON_Parsing", "Café Support", "x86_64 SIMD", "v2Api"] {
+        let feature = dashify_cargo_feature(name);
+        assert!(is_valid_feature(&feature), "{name:?} became {feature:?}");
+    }
+}
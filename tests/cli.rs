@@ -0,0 +1,312 @@
+use std::fs;
+use std::io::Write;
+use std::path::Path;
+use std::process::{Command, Output, Stdio};
+
+use dashify::{dashify, dashify_lossy, DashifyOptions};
+use tempfile::TempDir;
+
+const NAMES: &[&str] = &[
+    "My File.txt",
+    "myFileName.rs",
+    "XMLParser.java",
+    "HTTP2Request.go",
+    "already-clean.txt",
+    "snake_case_name.py",
+    "README",
+    "v1.2.3-release.tar.gz",
+    "Copy of Report (2).pdf",
+    "file~.txt",
+    "3rdParty Build.log",
+    "Q1 “Final” Report.docx",
+];
+
+fn dir_with(names: &[&str]) -> TempDir {
+    let dir = tempfile::tempdir().unwrap();
+    for name in names {
+        fs::write(dir.path().join(name), "").unwrap();
+    }
+    dir
+}
+
+// ignoring any settings from the environment the tests run in
+fn dashify_command(dir: &Path) -> Command {
+    let mut command = Command::new(env!("CARGO_BIN_EXE_dashify"));
+    command.current_dir(dir).arg("--no-env-file");
+    if cfg!(feature = "toml") {
+        command.arg("--no-config");
+    }
+    command
+}
+
+fn dashify_in(dir: &Path, args: &[&str]) -> Output {
+    dashify_command(dir).args(args).output().unwrap()
+}
+
+#[test]
+fn summary_reports_bytes_saved() {
+    let dir = dir_with(&["My  File.txt", "clean.txt"]);
+    let output = dashify_in(dir.path(), &["--summary", "."]);
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8_lossy(&output.stderr),
+        "Processed 2 files: 1 renamed, 1 unchanged, 0 errors; names are 1 bytes shorter\n"
+    );
+    assert!(dir.path().join("my-file.txt").exists());
+}
+
+#[test]
+fn stats_counts_files_skipped_by_the_conflict_strategy() {
+    let dir = dir_with(&["My File.txt", "my-file.txt"]);
+    let output = dashify_in(dir.path(), &["--stats", "--conflict-strategy", "skip", "."]);
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8_lossy(&output.stderr),
+        "Processed 2 files: 0 renamed, 1 unchanged, 1 skipped, 0 errors\n"
+    );
+    assert!(dir.path().join("My File.txt").exists());
+}
+
+#[test]
+fn stats_counts_files_renumbered_by_the_conflict_strategy() {
+    let dir = dir_with(&["My File.txt", "my-file.txt"]);
+    let output = dashify_in(dir.path(), &["--stats", "--conflict-strategy", "number", "."]);
+    assert!(output.status.success());
+    assert_eq!(
+        String::from_utf8_lossy(&output.stderr),
+        "Processed 2 files: 1 renamed (1 renumbered), 1 unchanged, 0 errors\n"
+    );
+    assert!(dir.path().join("my-file-2.txt").exists());
+}
+
+#[cfg(feature = "serde")]
+fn json_stdout(output: &Output) -> serde_json::Value {
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    serde_json::from_slice(&output.stdout).unwrap()
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn json_format_includes_the_stats() {
+    let dir = dir_with(&["My File.txt", "clean.txt"]);
+    let json = json_stdout(&dashify_in(dir.path(), &["--stats", "--format", "json", "."]));
+    assert_eq!(json["operations"][0]["from"], "./My File.txt");
+    assert_eq!(json["operations"][0]["to"], "./my-file.txt");
+    assert_eq!(json["stats"]["renamed"], 1);
+    assert_eq!(json["stats"]["unchanged"], 1);
+    assert_eq!(json["stats"]["errors"], serde_json::json!([]));
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn json_format_leaves_out_the_stats_without_stats() {
+    let dir = dir_with(&["My File.txt"]);
+    let json = json_stdout(&dashify_in(dir.path(), &["--format", "json", "."]));
+    assert_eq!(json["operations"].as_array().unwrap().len(), 1);
+    assert!(json.get("stats").is_none());
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn json_stats_list_skipped_and_renumbered_files() {
+    let dir = dir_with(&["My File.txt", "my-file.txt"]);
+    let args = ["--stats", "--format", "json", "--conflict-strategy", "skip", "."];
+    let json = json_stdout(&dashify_in(dir.path(), &args));
+    assert_eq!(json["stats"]["skipped"], serde_json::json!(["./My File.txt"]));
+    assert_eq!(json["stats"]["renumbered"], serde_json::json!([]));
+
+    let args = ["--stats", "--format", "json", "--conflict-strategy", "number", "."];
+    let json = json_stdout(&dashify_in(dir.path(), &args));
+    assert_eq!(json["stats"]["skipped"], serde_json::json!([]));
+    assert_eq!(json["stats"]["renumbered"][0]["to"], "./my-file-2.txt");
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn json_dry_run_lists_the_planned_renames_and_conflicts() {
+    let dir = dir_with(&["My File.txt", "my file.txt", "Other File.txt"]);
+    let json = json_stdout(&dashify_in(dir.path(), &["--dry-run", "--format", "json", "."]));
+    assert_eq!(json["operations"][0]["to"], "./other-file.txt");
+    assert_eq!(json["conflicts"][0]["target"], "./my-file.txt");
+    assert!(dir.path().join("Other File.txt").exists());
+}
+
+fn names_in(dir: &Path) -> Vec<String> {
+    let mut names: Vec<String> =
+        fs::read_dir(dir).unwrap().map(|entry| entry.unwrap().file_name().to_string_lossy().into_owned()).collect();
+    names.sort();
+    names
+}
+
+#[test]
+fn output_dir_gets_renamed_copies_and_the_sources_are_untouched() {
+    let source = dir_with(&["My File.txt", "clean.txt"]);
+    fs::write(source.path().join("My File.txt"), "contents").unwrap();
+    let target = tempfile::tempdir().unwrap();
+    let output_dir = target.path().join("new").join("out");
+
+    let output = dashify_in(source.path(), &["--output-dir", output_dir.to_str().unwrap(), "."]);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    assert_eq!(names_in(&output_dir), ["clean.txt", "my-file.txt"]);
+    assert_eq!(fs::read_to_string(output_dir.join("my-file.txt")).unwrap(), "contents");
+    assert_eq!(names_in(source.path()), ["My File.txt", "clean.txt"]);
+    assert_eq!(fs::read_to_string(source.path().join("My File.txt")).unwrap(), "contents");
+}
+
+#[test]
+fn output_dir_recreates_subdirectories_with_recursive() {
+    let source = dir_with(&["Top File.txt"]);
+    fs::create_dir_all(source.path().join("Sub Dir").join("Deeper")).unwrap();
+    fs::write(source.path().join("Sub Dir").join("Inner File.txt"), "").unwrap();
+    fs::write(source.path().join("Sub Dir").join("Deeper").join("Deep File.txt"), "").unwrap();
+    let target = tempfile::tempdir().unwrap();
+
+    let output = dashify_in(source.path(), &["-r", "--output-dir", target.path().to_str().unwrap(), "."]);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    assert_eq!(names_in(target.path()), ["Sub Dir", "top-file.txt"]);
+    assert_eq!(names_in(&target.path().join("Sub Dir")), ["Deeper", "inner-file.txt"]);
+    assert_eq!(names_in(&target.path().join("Sub Dir").join("Deeper")), ["deep-file.txt"]);
+    assert_eq!(names_in(&source.path().join("Sub Dir")), ["Deeper", "Inner File.txt"]);
+}
+
+#[test]
+fn output_dir_follows_the_conflict_strategy() {
+    let source = dir_with(&["My File.txt"]);
+    fs::write(source.path().join("My File.txt"), "new").unwrap();
+    let target = tempfile::tempdir().unwrap();
+    fs::write(target.path().join("my-file.txt"), "old").unwrap();
+    let output_dir = target.path().to_str().unwrap();
+
+    let output = dashify_in(source.path(), &["--output-dir", output_dir, "--conflict-strategy", "skip", "."]);
+    assert!(output.status.success());
+    assert_eq!(names_in(target.path()), ["my-file.txt"]);
+    assert_eq!(fs::read_to_string(target.path().join("my-file.txt")).unwrap(), "old");
+
+    let output = dashify_in(source.path(), &["--output-dir", output_dir, "--conflict-strategy", "number", "."]);
+    assert!(output.status.success());
+    assert_eq!(names_in(target.path()), ["my-file-2.txt", "my-file.txt"]);
+    assert_eq!(fs::read_to_string(target.path().join("my-file-2.txt")).unwrap(), "new");
+
+    let output = dashify_in(source.path(), &["--output-dir", output_dir, "."]);
+    assert!(!output.status.success());
+    assert_eq!(fs::read_to_string(target.path().join("my-file.txt")).unwrap(), "old");
+    assert_eq!(names_in(source.path()), ["My File.txt"]);
+}
+
+fn sorted_lines(output: &[u8]) -> Vec<String> {
+    let mut lines: Vec<String> = String::from_utf8_lossy(output).lines().map(str::to_string).collect();
+    lines.sort();
+    lines
+}
+
+#[test]
+fn verbose_prints_renames_and_skips_to_stdout() {
+    let dir = dir_with(&["My File.txt", "clean.txt"]);
+    let output = dashify_in(dir.path(), &["-v", "."]);
+    assert!(output.status.success());
+    assert_eq!(
+        sorted_lines(&output.stdout),
+        ["renamed: ./My File.txt → ./my-file.txt", "skipped: ./clean.txt (unchanged)"]
+    );
+    assert!(output.stderr.is_empty());
+}
+
+#[test]
+fn verbose_prints_errors_to_stderr() {
+    let dir = dir_with(&["Other Name.txt", "other-name.txt"]);
+    let output = dashify_in(dir.path(), &["--verbose", "."]);
+    assert!(!output.status.success());
+    assert_eq!(sorted_lines(&output.stdout), ["skipped: ./other-name.txt (unchanged)"]);
+    assert_eq!(
+        sorted_lines(&output.stderr),
+        ["error: ./Other Name.txt — './Other Name.txt' and './other-name.txt' would both become './other-name.txt'"]
+    );
+    assert!(dir.path().join("Other Name.txt").exists());
+}
+
+#[test]
+fn renames_are_silent_without_verbose() {
+    let dir = dir_with(&["My File.txt", "clean.txt"]);
+    let output = dashify_in(dir.path(), &["."]);
+    assert!(output.status.success());
+    assert!(output.stdout.is_empty());
+    assert!(dir.path().join("my-file.txt").exists());
+}
+
+#[test]
+fn dry_run_is_quiet_about_renames_even_when_verbose() {
+    let dir = dir_with(&["My File.txt"]);
+    let output = dashify_in(dir.path(), &["-v", "--dry-run", "."]);
+    assert!(output.status.success());
+    assert_eq!(String::from_utf8_lossy(&output.stdout), ".:\n  would rename: My File.txt → my-file.txt\n");
+    assert!(dir.path().join("My File.txt").exists());
+}
+
+fn dashify_stdin(input: &[u8], args: &[&str]) -> Output {
+    let dir = tempfile::tempdir().unwrap();
+    let mut child = dashify_command(dir.path())
+        .arg("--stdin")
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .unwrap();
+    child.stdin.take().unwrap().write_all(input).unwrap();
+    child.wait_with_output().unwrap()
+}
+
+#[test]
+fn stdin_output_matches_the_library() {
+    let output = dashify_stdin(NAMES.join("\n").as_bytes(), &[]);
+    assert!(output.status.success());
+    let expected: Vec<String> = NAMES.iter().map(|name| dashify(name, &DashifyOptions::default()).unwrap()).collect();
+    assert_eq!(String::from_utf8_lossy(&output.stdout).lines().collect::<Vec<_>>(), expected);
+}
+
+#[test]
+fn stdin_output_matches_the_library_with_options() {
+    let output = dashify_stdin(NAMES.join("\n").as_bytes(), &["--force-dash", "--separator", "_"]);
+    assert!(output.status.success());
+    let options = DashifyOptions {
+        force_dash: true,
+        separator: '_',
+        ..DashifyOptions::default()
+    };
+    let expected: Vec<String> = NAMES.iter().map(|name| dashify(name, &options).unwrap()).collect();
+    assert_eq!(String::from_utf8_lossy(&output.stdout).lines().collect::<Vec<_>>(), expected);
+}
+
+#[cfg(unix)]
+#[test]
+fn stdin_keeps_names_that_are_not_utf8() {
+    use std::ffi::OsStr;
+    use std::os::unix::ffi::OsStrExt;
+
+    let name = b"Caf\xe9 Menu.txt";
+    let output = dashify_stdin(&[&name[..], b"\0My File.txt\0"].concat(), &["-0"]);
+    assert!(output.status.success());
+    let expected = dashify_lossy(OsStr::from_bytes(name), &DashifyOptions::default());
+    assert_eq!(output.stdout, [expected.as_bytes(), b"\0my-file.txt\0"].concat());
+    assert!(output.stdout.contains(&0xe9));
+}
+
+#[test]
+fn acronym_flags_add_to_the_dictionary() {
+    let args = ["--acronym", "OAuth", "--acronym", "GraphQL"];
+    let output = dashify_stdin(b"MyOAuthClient.ts\nGraphQLServer.js\n", &args);
+    assert!(output.status.success());
+    assert_eq!(String::from_utf8_lossy(&output.stdout), "my-oauth-client.ts\ngraphql-server.js\n");
+}
+
+#[test]
+fn renamed_files_match_the_library() {
+    let dir = dir_with(NAMES);
+    let output = dashify_in(dir.path(), &["."]);
+    assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    for name in NAMES {
+        let expected = dashify(name, &DashifyOptions::default()).unwrap();
+        assert!(dir.path().join(&expected).exists(), "{name:?} should have become {expected:?}");
+    }
+    assert_eq!(fs::read_dir(dir.path()).unwrap().count(), NAMES.len());
+}
@@ -0,0 +1,60 @@
+use dashify::{dashify, is_compound_extension, DashifyOptions};
+
+const EXTENSIONS: &[&str] = &["ts", "d.ts", ".pb.go", "cmake.in"];
+
+#[test]
+fn the_longest_matching_extension_wins() {
+    assert_eq!(is_compound_extension("Types.d.ts", EXTENSIONS), Some(("Types", "d.ts")));
+    assert_eq!(is_compound_extension("index.ts", EXTENSIONS), Some(("index", "ts")));
+    assert_eq!(is_compound_extension("Config.cmake.in", EXTENSIONS), Some(("Config", "cmake.in")));
+}
+
+#[test]
+fn extensions_may_have_a_leading_dot() {
+    assert_eq!(is_compound_extension("API.pb.go", EXTENSIONS), Some(("API", "pb.go")));
+    assert_eq!(is_compound_extension("API.pb.go", &[".pb.go"]), is_compound_extension("API.pb.go", &["pb.go"]));
+}
+
+#[test]
+fn case_is_ignored_and_kept() {
+    assert_eq!(is_compound_extension("TYPES.D.TS", EXTENSIONS), Some(("TYPES", "D.TS")));
+}
+
+#[test]
+fn the_extension_must_end_the_name() {
+    assert_eq!(is_compound_extension("notes.txt", EXTENSIONS), None);
+    assert_eq!(is_compound_extension("types.d.ts.bak", EXTENSIONS), None);
+    assert_eq!(is_compound_extension("types-d.ts", &["d.ts"]), None);
+}
+
+#[test]
+fn something_must_be_left_of_the_stem() {
+    assert_eq!(is_compound_extension("d.ts", &["d.ts"]), None);
+    assert_eq!(is_compound_extension(".d.ts", &["d.ts"]), None);
+    assert_eq!(is_compound_extension(".d.ts", EXTENSIONS), Some((".d", "ts")));
+    assert_eq!(is_compound_extension("a.b", &[""]), None);
+}
+
+#[test]
+fn non_ascii_stems_are_kept() {
+    assert_eq!(is_compound_extension("café.d.ts", EXTENSIONS), Some(("café", "d.ts")));
+}
+
+#[test]
+fn the_defaults_are_the_tar_archives() {
+    let defaults = DashifyOptions::default().compound_extensions;
+    assert_eq!(defaults, ["tar.gz", "tar.bz2", "tar.xz"]);
+    assert_eq!(is_compound_extension("backup.tar.xz", &defaults), Some(("backup", "tar.xz")));
+}
+
+#[test]
+fn compound_extensions_are_kept_whole_when_the_stem_is_shortened() {
+    let with = |compound_extensions: &[&str]| DashifyOptions {
+        compound_extensions: compound_extensions.iter().map(|extension| extension.to_string()).collect(),
+        max_length: Some(6),
+        ..DashifyOptions::default()
+    };
+    assert_eq!(dashify("Component Props.d.ts", &with(&["d.ts"])).unwrap(), "compon.d.ts");
+    assert_eq!(dashify("Component Props.d.ts", &with(&[])).unwrap(), "compon.ts");
+    assert_eq!(dashify("API Client.pb.go", &with(EXTENSIONS)).unwrap(), "api.pb.go");
+}
@@ -0,0 +1,78 @@
+#![cfg(feature = "toml")]
+
+use dashify::{DashifyOptions, TildeHandling};
+
+// every serialized setting changed from its default
+fn fully_populated() -> DashifyOptions {
+    DashifyOptions {
+        force_dash: true,
+        require_change: true,
+        normalize_quotes: false,
+        normalize_dashes: false,
+        strip_zero_width: false,
+        min_entropy: Some(3.5),
+        preserve_timestamps: true,
+        skip_resource_forks: false,
+        leave_resource_forks_alone: false,
+        tilde_handling: TildeHandling::ToHyphen,
+        force_ascii: true,
+        respect_dotenv: false,
+        strip_noise_words: true,
+        report_non_ascii_separately: true,
+        abbreviation_min_length: 3,
+        acronym_dictionary: vec!["OAuth".to_string(), "GraphQL".to_string()],
+        preserve_ordinals: false,
+        preserve_hex: false,
+        preserve_uuid: false,
+        preserve_dates: false,
+        max_stem_words: Some(4),
+        separator: '_',
+        transliterate: true,
+        strip_diacritics: false,
+        #[cfg(feature = "regex-patterns")]
+        preserve_patterns: vec!["Makefile".to_string(), r"go\.(mod|sum)".to_string()],
+        compound_extensions: vec!["d.ts".to_string(), "pb.go".to_string()],
+        max_length: Some(63),
+        slug_mode: true,
+        ..DashifyOptions::default()
+    }
+}
+
+#[test]
+fn options_round_trip_through_toml() {
+    let options = fully_populated();
+    let toml = toml::to_string(&options).unwrap();
+    let parsed = DashifyOptions::from_toml(&toml).unwrap();
+    assert_eq!(format!("{parsed:?}"), format!("{options:?}"));
+}
+
+#[test]
+fn default_options_round_trip_through_toml() {
+    let toml = toml::to_string(&DashifyOptions::default()).unwrap();
+    let parsed = DashifyOptions::from_toml(&toml).unwrap();
+    assert_eq!(format!("{parsed:?}"), format!("{:?}", DashifyOptions::default()));
+}
+
+#[test]
+fn keys_are_snake_case_field_names() {
+    let toml = toml::to_string(&fully_populated()).unwrap();
+    assert!(toml.contains("force_dash = true\n"), "{toml}");
+    assert!(toml.contains("tilde_handling = \"to-hyphen\"\n"), "{toml}");
+    assert!(toml.contains("max_length = 63\n"), "{toml}");
+    assert!(!toml.contains("filter_fn"), "{toml}");
+}
+
+#[test]
+fn cli_flag_names_are_accepted_as_keys() {
+    let options = DashifyOptions::from_toml("compound_ext = [\"d.ts\"]\nacronym = [\"OAuth\"]\n").unwrap();
+    assert_eq!(options.compound_extensions, ["d.ts"]);
+    assert_eq!(options.acronym_dictionary, ["OAuth"]);
+}
+
+#[test]
+fn missing_keys_keep_their_defaults_and_unknown_keys_fail() {
+    let options = DashifyOptions::from_toml("separator = \"_\"\n").unwrap();
+    assert_eq!(options.separator, '_');
+    assert!(options.normalize_quotes);
+    assert!(DashifyOptions::from_toml("no_such_setting = true\n").is_err());
+}
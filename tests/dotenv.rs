@@ -0,0 +1,27 @@
+use std::fs;
+
+use dashify::DashifyOptions;
+
+#[test]
+fn dotenv_settings_are_applied() {
+    let dir = tempfile::tempdir().unwrap();
+    fs::write(dir.path().join(".env"), "DASHIFY_FORCE_DASH=true\nDASHIFY_SEPARATOR=_\nOTHER=1\n").unwrap();
+    let mut options = DashifyOptions::default();
+    assert_eq!(options.apply_dotenv(dir.path()), []);
+    assert!(options.force_dash);
+    assert_eq!(options.separator, '_');
+}
+
+#[test]
+fn invalid_dotenv_settings_are_returned_and_left_out() {
+    let dir = tempfile::tempdir().unwrap();
+    let env_file = dir.path().join(".env");
+    fs::write(&env_file, "DASHIFY_FORCE_DASH=maybe\nDASHIFY_FORCE_ASCII=true\n").unwrap();
+    let mut options = DashifyOptions::default();
+    let errors = options.apply_dotenv(dir.path());
+    assert_eq!(errors.len(), 1);
+    assert_eq!(errors[0].path.as_deref(), Some(env_file.canonicalize().unwrap().as_path()));
+    assert!(errors[0].message.contains("DASHIFY_FORCE_DASH"), "{}", errors[0]);
+    assert!(!options.force_dash);
+    assert!(options.force_ascii);
+}
@@ -0,0 +1,55 @@
+use dashify::{dashify_bytes, detect_encoding, DashifyOptions, Encoding};
+
+fn dashify_default(name: &[u8], encoding: Encoding) -> Vec<u8> {
+    dashify_bytes(name, encoding, &DashifyOptions::default())
+}
+
+#[test]
+fn encodings_are_detected() {
+    assert_eq!(detect_encoding("Café Menu.txt".as_bytes()), Encoding::Utf8);
+    assert_eq!(detect_encoding(b"\xEF\xBB\xBFnotes.txt"), Encoding::Utf8);
+    assert_eq!(detect_encoding(b"Caf\xe9 Menu.txt"), Encoding::Latin1);
+    assert_eq!(detect_encoding(b"R\xe9sum\xe9 \x96 Final.doc"), Encoding::Cp1252);
+    // 0x81 is undefined in Windows-1252 and a control character in Latin-1
+    assert_eq!(detect_encoding(b"odd\x81\xe9.txt"), Encoding::Unknown);
+}
+
+#[test]
+fn latin1_names_are_dashified_keeping_their_letters() {
+    assert_eq!(dashify_default(b"Caf\xe9 Menu.txt", Encoding::Latin1), b"caf\xe9-menu.txt");
+    assert_eq!(dashify_default(b"\xc9T\xc9 Photos.JPG", Encoding::Latin1), b"\xe9t\xe9-photos.jpg");
+}
+
+#[test]
+fn cp1252_names_are_dashified_keeping_their_letters() {
+    // 0x96 is an en dash, which becomes a plain separator
+    assert_eq!(dashify_default(b"R\xe9sum\xe9 \x96 Final.doc", Encoding::Cp1252), b"r\xe9sum\xe9-final.doc");
+    assert_eq!(dashify_default(b"\x8aKODA Car.txt", Encoding::Cp1252), b"\x9akoda-car.txt");
+}
+
+#[test]
+fn legacy_names_still_follow_the_other_leave_alone_rules() {
+    assert_eq!(dashify_default(b"README", Encoding::Latin1), b"README");
+    assert_eq!(dashify_default(b"__init__.py", Encoding::Latin1), b"__init__.py");
+}
+
+#[test]
+fn utf8_names_are_handled_like_dashify() {
+    assert_eq!(dashify_default(b"My File.txt", Encoding::Utf8), b"my-file.txt");
+    assert_eq!(dashify_default("Café Menu.txt".as_bytes(), Encoding::Utf8), "Café Menu.txt".as_bytes());
+}
+
+#[test]
+fn undecodable_names_are_unchanged() {
+    assert_eq!(dashify_default(b"odd\x81\xe9 Name.txt", Encoding::Unknown), b"odd\x81\xe9 Name.txt");
+    assert_eq!(dashify_default(b"Caf\xe9 Menu.txt", Encoding::Utf8), b"Caf\xe9 Menu.txt");
+}
+
+#[test]
+fn lossy_names_that_fail_come_back_unchanged() {
+    use std::ffi::OsStr;
+
+    let options = DashifyOptions::default();
+    assert_eq!(dashify::dashify_lossy(OsStr::new("---.txt"), &options), "---.txt");
+    assert_eq!(dashify::dashify_lossy(OsStr::new("My File.txt"), &options), "my-file.txt");
+}
@@ -0,0 +1,61 @@
+use dashify::test_helpers::assert_valid_env_var;
+use dashify::{dashify_env_var, dashify_environment, DashifyError, DashifyOptions};
+
+fn env_var(name: &str) -> String {
+    dashify_env_var(name, &DashifyOptions::default())
+}
+
+#[test]
+fn valid_names_are_unchanged() {
+    assert_eq!(env_var("DATABASE_URL"), "DATABASE_URL");
+    assert_eq!(env_var("_PRIVATE"), "_PRIVATE");
+}
+
+#[test]
+fn names_are_converted_to_upper_snake_case() {
+    assert_eq!(env_var("database-url"), "DATABASE_URL");
+    assert_eq!(env_var("app.log.level"), "APP_LOG_LEVEL");
+    assert_eq!(env_var("maxRetryCount"), "MAX_RETRY_COUNT");
+}
+
+#[test]
+fn characters_outside_the_env_var_alphabet_become_underscores() {
+    assert_eq!(env_var("a/b"), "A_B");
+    assert_eq!(env_var("a b"), "A_B");
+    assert_eq!(env_var("café"), "CAF");
+    assert_eq!(env_var("a//b"), "A_B");
+    for name in ["a/b", "a b", "café", "2fa-code", "x@y!z", "über-cool", "-.-"] {
+        assert_valid_env_var(&env_var(name));
+    }
+}
+
+#[test]
+fn leading_digit_gets_an_underscore() {
+    assert_eq!(env_var("2fa-code"), "_2_FA_CODE");
+}
+
+#[test]
+fn environment_keys_are_converted_and_values_kept() {
+    let vars = vec![
+        ("database-url".to_string(), "postgres://".to_string()),
+        ("HOME".to_string(), "/root".to_string()),
+    ];
+    let converted = dashify_environment(vars, &DashifyOptions::default()).unwrap();
+    assert_eq!(
+        converted,
+        vec![
+            ("DATABASE_URL".to_string(), "postgres://".to_string()),
+            ("HOME".to_string(), "/root".to_string()),
+        ]
+    );
+}
+
+#[test]
+fn environment_key_collisions_are_reported() {
+    let vars = vec![
+        ("db-url".to_string(), "a".to_string()),
+        ("db.url".to_string(), "b".to_string()),
+    ];
+    let error = dashify_environment(vars, &DashifyOptions::default()).unwrap_err();
+    assert!(matches!(error, DashifyError::CollisionDetected { target, .. } if target == "DB_URL"));
+}
@@ -0,0 +1,16 @@
+use dashify::dashify_header_name;
+
+#[test]
+fn snake_and_camel_case_become_title_kebab_case() {
+    assert_eq!(dashify_header_name("x_request_id"), "X-Request-Id");
+    assert_eq!(dashify_header_name("contentType"), "Content-Type");
+    assert_eq!(dashify_header_name("userAgent"), "User-Agent");
+}
+
+#[test]
+fn any_case_is_normalized() {
+    assert_eq!(dashify_header_name("CONTENT-TYPE"), "Content-Type");
+    assert_eq!(dashify_header_name("content-type"), "Content-Type");
+    assert_eq!(dashify_header_name("X-Forwarded-For"), "X-Forwarded-For");
+    assert_eq!(dashify_header_name("etag"), "Etag");
+}
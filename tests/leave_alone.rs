@@ -0,0 +1,28 @@
+use dashify::{dashify, DashifyOptions};
+
+fn dashify_default(name: &str) -> String {
+    dashify(name, &DashifyOptions::default()).unwrap()
+}
+
+#[test]
+fn single_all_caps_word_is_left_alone() {
+    assert_eq!(dashify_default("README"), "README");
+    assert_eq!(dashify_default("CHANGELOG.md"), "CHANGELOG.md");
+    assert_eq!(dashify_default("MAKEFILE"), "MAKEFILE");
+}
+
+#[test]
+fn all_caps_words_joined_by_underscores_are_processed() {
+    assert_eq!(dashify_default("MY_CONSTANT.txt"), "my_constant.txt");
+    assert_eq!(dashify_default("MAX_VALUE.txt"), "max_value.txt");
+    assert_eq!(dashify_default("API_KEY"), "api_key");
+}
+
+#[test]
+fn all_caps_words_with_force_dash_get_dashes() {
+    let options = DashifyOptions {
+        force_dash: true,
+        ..DashifyOptions::default()
+    };
+    assert_eq!(dashify("MY_CONSTANT.txt", &options).unwrap(), "my-constant.txt");
+}
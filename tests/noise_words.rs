@@ -0,0 +1,42 @@
+use dashify::{dashify, strip_common_noise_words, DashifyOptions};
+
+fn dashify_stripping_noise(name: &str) -> String {
+    let options = DashifyOptions {
+        strip_noise_words: true,
+        ..DashifyOptions::default()
+    };
+    dashify(name, &options).unwrap()
+}
+
+#[test]
+fn noise_is_stripped_before_dashifying() {
+    assert_eq!(dashify_stripping_noise("Copy of My Document.docx"), "my-document.docx");
+    assert_eq!(dashify_stripping_noise("Report Final Final v2.pdf"), "report.pdf");
+}
+
+#[test]
+fn download_and_copy_artifacts_are_noise() {
+    assert_eq!(strip_common_noise_words("Photo (2).jpg"), "Photo.jpg");
+    assert_eq!(strip_common_noise_words("Notes [1].txt"), "Notes.txt");
+    assert_eq!(strip_common_noise_words("Budget - Copy.xlsx"), "Budget.xlsx");
+    assert_eq!(strip_common_noise_words("Budget - Copy (3).xlsx"), "Budget.xlsx");
+    assert_eq!(strip_common_noise_words("copy of notes.txt"), "notes.txt");
+    assert_eq!(strip_common_noise_words("Plan FINAL V3.txt"), "Plan.txt");
+}
+
+#[test]
+fn names_that_are_all_noise_are_unchanged() {
+    assert_eq!(strip_common_noise_words("Final.txt"), "Final.txt");
+    assert_eq!(strip_common_noise_words("v2.txt"), "v2.txt");
+}
+
+#[test]
+fn noise_words_must_be_whole_words() {
+    assert_eq!(strip_common_noise_words("Finality v2x.txt"), "Finality v2x.txt");
+    assert_eq!(strip_common_noise_words("Copycat.txt"), "Copycat.txt");
+}
+
+#[test]
+fn noise_stripping_is_off_by_default() {
+    assert_eq!(dashify("Copy of My Document.docx", &DashifyOptions::default()).unwrap(), "copy-of-my-document.docx");
+}
@@ -0,0 +1,124 @@
+use dashify::{dashify, normalize_unicode_punctuation, DashifyOptions};
+
+fn force_ascii() -> DashifyOptions {
+    DashifyOptions {
+        force_ascii: true,
+        ..DashifyOptions::default()
+    }
+}
+
+#[test]
+fn non_ascii_names_are_left_alone_by_default() {
+    let options = DashifyOptions::default();
+    assert_eq!(dashify("Ünter-Wasser.txt", &options).unwrap(), "Ünter-Wasser.txt");
+    assert_eq!(dashify("Café Menu.txt", &options).unwrap(), "Café Menu.txt");
+}
+
+#[test]
+fn force_ascii_strips_non_ascii_characters() {
+    assert_eq!(dashify("Ünter-Wasser.txt", &force_ascii()).unwrap(), "nter-wasser.txt");
+    assert_eq!(dashify("naïve.txt", &force_ascii()).unwrap(), "nave.txt");
+    assert_eq!(dashify("Москва Report.txt", &force_ascii()).unwrap(), "report.txt");
+}
+
+#[test]
+fn force_ascii_leaves_names_with_nothing_ascii_alone() {
+    assert_eq!(dashify("日本.txt", &force_ascii()).unwrap(), "日本.txt");
+}
+
+#[test]
+fn transliteration_comes_before_stripping() {
+    let options = DashifyOptions {
+        transliterate: true,
+        ..force_ascii()
+    };
+    assert_eq!(dashify("Ünter-Wasser.txt", &options).unwrap(), "unter-wasser.txt");
+    assert_eq!(dashify("naïve.txt", &options).unwrap(), "naive.txt");
+}
+
+#[test]
+fn figure_en_and_em_dashes_become_separators() {
+    let options = DashifyOptions::default();
+    assert_eq!(dashify("my\u{2012}report.docx", &options).unwrap(), "my-report.docx");
+    assert_eq!(dashify("my\u{2013}report.docx", &options).unwrap(), "my-report.docx");
+    assert_eq!(dashify("my\u{2014}report.docx", &options).unwrap(), "my-report.docx");
+    assert_eq!(dashify("A\u{2014}B\u{2013}C\u{2012}D.txt", &options).unwrap(), "a-b-c-d.txt");
+}
+
+#[test]
+fn dashes_between_spaces_collapse_into_one_separator() {
+    let options = DashifyOptions::default();
+    assert_eq!(dashify("Chapter 1 \u{2014} Intro.md", &options).unwrap(), "chapter-1-intro.md");
+}
+
+#[test]
+fn dashes_are_left_alone_without_normalize_dashes() {
+    let options = DashifyOptions {
+        normalize_dashes: false,
+        ..DashifyOptions::default()
+    };
+    for name in ["my\u{2012}report.docx", "my\u{2013}report.docx", "my\u{2014}report.docx"] {
+        assert_eq!(dashify(name, &options).unwrap(), name);
+    }
+}
+
+#[test]
+fn curly_quotes_become_separators() {
+    let options = DashifyOptions::default();
+    assert_eq!(dashify("it\u{2019}s mine.txt", &options).unwrap(), "it-s-mine.txt");
+    assert_eq!(dashify("it\u{2019}s mine.txt", &options).unwrap(), dashify("it's mine.txt", &options).unwrap());
+    assert_eq!(dashify("\u{2018}Draft\u{2019}.txt", &options).unwrap(), "draft.txt");
+    assert_eq!(dashify("\u{201C}Draft\u{201D}.txt", &options).unwrap(), "draft.txt");
+    assert_eq!(dashify("Say \u{201C}Hi\u{201D} Now.md", &options).unwrap(), "say-hi-now.md");
+}
+
+#[test]
+fn curly_quotes_are_left_alone_without_normalize_quotes() {
+    let options = DashifyOptions {
+        normalize_quotes: false,
+        ..DashifyOptions::default()
+    };
+    for name in ["it\u{2019}s mine.txt", "\u{2018}Draft\u{2019}.txt", "\u{201C}Draft\u{201D}.txt"] {
+        assert_eq!(dashify(name, &options).unwrap(), name);
+    }
+}
+
+#[test]
+fn unicode_punctuation_is_normalized_to_ascii() {
+    assert_eq!(normalize_unicode_punctuation("\u{201C}Q1\u{2013}Q2\u{201D} report"), "\"Q1-Q2\" report");
+    assert_eq!(normalize_unicode_punctuation("it\u{2019}s \u{2018}mine\u{2019}"), "it's 'mine'");
+    assert_eq!(normalize_unicode_punctuation("a\u{2012}b\u{2014}c"), "a-b-c");
+    assert_eq!(normalize_unicode_punctuation("caf\u{e9} \u{200B}"), "caf\u{e9} \u{200B}");
+}
+
+#[test]
+fn names_differing_only_by_a_zero_width_character_dashify_alike() {
+    let options = DashifyOptions::default();
+    for invisible in ['\u{200B}', '\u{200C}', '\u{200D}', '\u{FEFF}'] {
+        assert_eq!(dashify(&format!("my-{invisible}file.txt"), &options).unwrap(), "my-file.txt");
+        assert_eq!(dashify(&format!("My{invisible} File.txt"), &options).unwrap(), "my-file.txt");
+        assert_eq!(dashify(&format!("{invisible}reportFinal.pdf"), &options).unwrap(), "report-final.pdf");
+    }
+}
+
+#[test]
+fn zero_width_characters_inside_a_word_are_removed() {
+    let options = DashifyOptions::default();
+    assert_eq!(dashify("my\u{200B}file.txt", &options).unwrap(), "myfile.txt");
+    assert_eq!(dashify("\u{FEFF}notes.txt", &options).unwrap(), "notes.txt");
+}
+
+#[test]
+fn names_still_non_ascii_after_stripping_are_left_alone() {
+    let options = DashifyOptions::default();
+    assert_eq!(dashify("caf\u{200B}é.txt", &options).unwrap(), "caf\u{200B}é.txt");
+}
+
+#[test]
+fn zero_width_characters_are_kept_without_strip_zero_width() {
+    let options = DashifyOptions {
+        strip_zero_width: false,
+        ..DashifyOptions::default()
+    };
+    assert_eq!(dashify("my\u{200B}file.txt", &options).unwrap(), "my\u{200B}file.txt");
+}
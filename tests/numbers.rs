@@ -0,0 +1,156 @@
+use dashify::{dashify, DashifyOptions};
+
+fn dashify_default(name: &str) -> String {
+    dashify(name, &DashifyOptions::default()).unwrap()
+}
+
+#[test]
+fn digits_split_from_letters_after_camel_case_splitting() {
+    assert_eq!(dashify_default("HTTP2Request.java"), "http-2-request.java");
+    assert_eq!(dashify_default("getV8Engine.txt"), "get-v-8-engine.txt");
+    assert_eq!(dashify_default("load3DModel.txt"), "load-3-d-model.txt");
+}
+
+#[test]
+fn existing_separator_before_a_digit_is_not_doubled() {
+    assert_eq!(dashify_default("file-2-name.txt"), "file-2-name.txt");
+    assert_eq!(dashify_default("File-2-Name.txt"), "file-2-name.txt");
+    assert_eq!(dashify_default("File_2Name.txt"), "file_2-name.txt");
+}
+
+#[test]
+fn ordinal_suffixes_stay_with_their_number() {
+    assert_eq!(dashify_default("1stReport.txt"), "1st-report.txt");
+    assert_eq!(dashify_default("2ndPlace.txt"), "2nd-place.txt");
+    assert_eq!(dashify_default("3rdParty.txt"), "3rd-party.txt");
+    assert_eq!(dashify_default("4thFloor.txt"), "4th-floor.txt");
+    assert_eq!(dashify_default("21stCentury.txt"), "21st-century.txt");
+    assert_eq!(dashify_default("11thHour.txt"), "11th-hour.txt");
+}
+
+#[test]
+fn suffixes_that_do_not_fit_the_number_are_split() {
+    assert_eq!(dashify_default("1ndThing.txt"), "1-nd-thing.txt");
+    assert_eq!(dashify_default("2stX.txt"), "2-st-x.txt");
+}
+
+#[test]
+fn ordinals_are_split_without_preserve_ordinals() {
+    let options = DashifyOptions {
+        preserve_ordinals: false,
+        ..DashifyOptions::default()
+    };
+    assert_eq!(dashify("1stReport.txt", &options).unwrap(), "1-st-report.txt");
+    assert_eq!(dashify("2ndPlace.txt", &options).unwrap(), "2-nd-place.txt");
+    assert_eq!(dashify("3rdParty.txt", &options).unwrap(), "3-rd-party.txt");
+    assert_eq!(dashify("4thFloor.txt", &options).unwrap(), "4-th-floor.txt");
+}
+
+#[test]
+fn hex_literals_are_kept_whole() {
+    assert_eq!(dashify_default("0xFF.bin"), "0xff.bin");
+    assert_eq!(dashify_default("0xDEAD.bin"), "0xdead.bin");
+    assert_eq!(dashify_default("0xCAFEBABE.bin"), "0xcafebabe.bin");
+    assert_eq!(dashify_default("firmware_0x1A2B.hex"), "firmware_0x1a2b.hex");
+    assert_eq!(dashify_default("0xDEADBEEF-patch.bin"), "0xdeadbeef-patch.bin");
+}
+
+#[test]
+fn uppercase_hex_literals_are_lowercased_not_left_alone() {
+    assert_eq!(dashify_default("0X1A2B.bin"), "0x1a2b.bin");
+    assert_eq!(dashify_default("0XFF"), "0xff");
+    assert_eq!(dashify_default("fw_0X1A2B.hex"), "fw_0x1a2b.hex");
+    assert_eq!(dashify_default("DEADBEEF.bin"), "DEADBEEF.bin");
+}
+
+#[test]
+fn hex_literals_are_split_without_preserve_hex() {
+    let options = DashifyOptions {
+        preserve_hex: false,
+        ..DashifyOptions::default()
+    };
+    assert_eq!(dashify("0xDEAD.bin", &options).unwrap(), "0-xdead.bin");
+    assert_eq!(dashify("firmware_0x1A2B.hex", &options).unwrap(), "firmware_0-x-1-a-2-b.hex");
+    assert_eq!(dashify("0X1A2B.bin", &options).unwrap(), "0X1A2B.bin");
+}
+
+#[test]
+fn hyphenated_uuids_are_only_lowercased() {
+    assert_eq!(
+        dashify_default("550E8400-E29B-41D4-A716-446655440000.JSON"),
+        "550e8400-e29b-41d4-a716-446655440000.json"
+    );
+    assert_eq!(
+        dashify_default("550e8400-e29b-41d4-a716-446655440000.json"),
+        "550e8400-e29b-41d4-a716-446655440000.json"
+    );
+    assert_eq!(
+        dashify_default("backup_550E8400-E29B-41D4-A716-446655440000.tar.gz"),
+        "backup_550e8400-e29b-41d4-a716-446655440000.tar.gz"
+    );
+}
+
+#[test]
+fn bare_uuids_are_only_lowercased() {
+    assert_eq!(dashify_default("550E8400E29B41D4A716446655440000.JSON"), "550e8400e29b41d4a716446655440000.json");
+    assert_eq!(dashify_default("550e8400e29b41d4a716446655440000.json"), "550e8400e29b41d4a716446655440000.json");
+    assert_eq!(
+        dashify_default("Backup 550E8400E29B41D4A716446655440000.zip"),
+        "backup-550e8400e29b41d4a716446655440000.zip"
+    );
+}
+
+#[test]
+fn uuids_are_split_without_preserve_uuid() {
+    let options = DashifyOptions {
+        preserve_uuid: false,
+        ..DashifyOptions::default()
+    };
+    assert_eq!(
+        dashify("550E8400-E29B-41D4-A716-446655440000.JSON", &options).unwrap(),
+        "550-e-8400-e-29-b-41-d-4-a-716-446655440000.json"
+    );
+    assert_eq!(
+        dashify("550E8400E29B41D4A716446655440000.JSON", &options).unwrap(),
+        "550E8400E29B41D4A716446655440000.JSON"
+    );
+}
+
+#[test]
+fn numeric_dates_stay_whole() {
+    assert_eq!(dashify_default("Backup20240115.tar.gz"), "backup-20240115.tar.gz");
+    assert_eq!(dashify_default("MeetingNotes20240115.txt"), "meeting-notes-20240115.txt");
+    assert_eq!(dashify_default("Summary2024-01-15Final.txt"), "summary-2024-01-15-final.txt");
+    assert_eq!(dashify_default("2024-01-15Report.pdf"), "2024-01-15-report.pdf");
+    assert_eq!(dashify_default("Statement202401.pdf"), "statement-202401.pdf");
+    assert_eq!(dashify_default("Invoice202401Paid.pdf"), "invoice-202401-paid.pdf");
+    assert_eq!(dashify_default("report_2024-01-15.pdf"), "report_2024-01-15.pdf");
+}
+
+#[test]
+fn dates_with_month_names_stay_whole() {
+    assert_eq!(dashify_default("meeting2024Jan15.docx"), "meeting-2024jan15.docx");
+    assert_eq!(dashify_default("Notes2024January15.txt"), "notes-2024january15.txt");
+    assert_eq!(dashify_default("Photo15Jan2024.jpg"), "photo-15jan2024.jpg");
+    assert_eq!(dashify_default("Trip2024Sept3.txt"), "trip-2024sept3.txt");
+    assert_eq!(dashify_default("Trip 2024Sept3Notes.txt"), "trip-2024sept3-notes.txt");
+}
+
+#[test]
+fn a_month_name_must_end_its_word() {
+    assert_eq!(dashify_default("Party2024Janet.txt"), "party-2024-janet.txt");
+    assert_eq!(dashify_default("Log2024Foo15.txt"), "log-2024-foo-15.txt");
+}
+
+#[test]
+fn dates_are_split_without_preserve_dates() {
+    let options = DashifyOptions {
+        preserve_dates: false,
+        ..DashifyOptions::default()
+    };
+    assert_eq!(dashify("meeting2024Jan15.docx", &options).unwrap(), "meeting-2024-jan-15.docx");
+    assert_eq!(dashify("Notes2024January15.txt", &options).unwrap(), "notes-2024-january-15.txt");
+    assert_eq!(dashify("Photo15Jan2024.jpg", &options).unwrap(), "photo-15-jan-2024.jpg");
+    assert_eq!(dashify("Trip2024Sept3.txt", &options).unwrap(), "trip-2024-sept-3.txt");
+    assert_eq!(dashify("Summary2024-01-15Final.txt", &options).unwrap(), "summary-2024-01-15-final.txt");
+}
@@ -0,0 +1,131 @@
+use std::fs;
+use std::path::Path;
+use std::process::Command;
+
+use dashify::{compute_rename_plan, DashifyOptions, RenameOperation, RenamePlan};
+use tempfile::TempDir;
+
+fn dir_with(names: &[&str]) -> TempDir {
+    let dir = tempfile::tempdir().unwrap();
+    for name in names {
+        let path = dir.path().join(name);
+        fs::create_dir_all(path.parent().unwrap()).unwrap();
+        fs::write(path, "").unwrap();
+    }
+    dir
+}
+
+// ignoring any settings from the environment the tests run in
+fn dashify_command() -> Command {
+    let mut command = Command::new(env!("CARGO_BIN_EXE_dashify"));
+    command.arg("--no-env-file");
+    if cfg!(feature = "toml") {
+        command.arg("--no-config");
+    }
+    command
+}
+
+fn names(dir: &Path) -> Vec<String> {
+    let mut names: Vec<String> = fs::read_dir(dir)
+        .unwrap()
+        .map(|entry| entry.unwrap().file_name().to_string_lossy().into_owned())
+        .collect();
+    names.sort();
+    names
+}
+
+#[test]
+fn plan_lists_renames_and_unchanged_files_in_path_order() {
+    let dir = dir_with(&["kebab-name.txt", "B Side.txt", "clean.txt", "Sub Dir/Inner Name.md"]);
+    let root = dir.path();
+    let plan = compute_rename_plan(root, true, &DashifyOptions::default()).unwrap();
+
+    assert_eq!(
+        plan.operations,
+        vec![
+            RenameOperation::new(root.join("B Side.txt"), root.join("b-side.txt")),
+            RenameOperation::new(root.join("Sub Dir/Inner Name.md"), root.join("Sub Dir/inner-name.md")),
+        ]
+    );
+    assert!(plan.conflicts.is_empty());
+    assert_eq!(plan.unchanged, vec![root.join("clean.txt"), root.join("kebab-name.txt")]);
+    assert_eq!(plan.stats.processed, 4);
+    assert_eq!(plan.stats.renamed, 2);
+}
+
+#[test]
+fn plan_reports_names_that_would_collide() {
+    let dir = dir_with(&["My File.txt", "my-file.TXT", "other.txt"]);
+    let root = dir.path();
+    let plan = compute_rename_plan(root, false, &DashifyOptions::default()).unwrap();
+
+    assert!(plan.operations.is_empty());
+    assert_eq!(plan.conflicts.len(), 1);
+    assert_eq!(plan.conflicts[0].target, root.join("my-file.txt"));
+    assert_eq!(plan.needs_renaming(), vec![root.join("My File.txt"), root.join("my-file.TXT")]);
+}
+
+#[cfg(unix)]
+#[test]
+fn a_dangling_symlink_takes_its_name() {
+    let dir = dir_with(&["My File.txt"]);
+    let root = dir.path();
+    std::os::unix::fs::symlink(root.join("missing"), root.join("my-file.txt")).unwrap();
+    let plan = RenamePlan::for_files(&[root.join("My File.txt")], &DashifyOptions::default());
+
+    assert!(plan.operations.is_empty());
+    assert_eq!(plan.conflicts.len(), 1);
+    assert_eq!(plan.conflicts[0].target, root.join("my-file.txt"));
+}
+
+#[cfg(feature = "serde")]
+#[test]
+fn plan_serializes_to_json() {
+    let dir = dir_with(&["My File.txt"]);
+    let plan = compute_rename_plan(dir.path(), false, &DashifyOptions::default()).unwrap();
+    let json = serde_json::to_value(&plan).unwrap();
+
+    assert_eq!(json["operations"][0]["to"], dir.path().join("my-file.txt").to_string_lossy().as_ref());
+    assert_eq!(json["conflicts"], serde_json::json!([]));
+    assert_eq!(json["stats"]["renamed"], 1);
+}
+
+#[test]
+fn dry_run_renames_nothing() {
+    let dir = dir_with(&["My File.txt", "clean.txt"]);
+    let output = dashify_command()
+        .arg("--dry-run")
+        .arg(dir.path())
+        .output()
+        .unwrap();
+
+    assert!(output.status.success());
+    assert!(String::from_utf8_lossy(&output.stdout).contains("would rename: My File.txt → my-file.txt"));
+    assert_eq!(names(dir.path()), ["My File.txt", "clean.txt"]);
+}
+
+#[test]
+fn atomic_renames_everything() {
+    let dir = dir_with(&["My File.txt", "Other File.txt", "clean.txt"]);
+    let status = dashify_command()
+        .arg("--atomic")
+        .arg(dir.path())
+        .status()
+        .unwrap();
+
+    assert!(status.success());
+    assert_eq!(names(dir.path()), ["clean.txt", "my-file.txt", "other-file.txt"]);
+}
+
+#[test]
+fn atomic_renames_nothing_when_names_collide() {
+    let dir = dir_with(&["My File.txt", "my-file.TXT", "Other File.txt"]);
+    let status = dashify_command()
+        .arg("--atomic")
+        .arg(dir.path())
+        .status()
+        .unwrap();
+
+    assert!(!status.success());
+    assert_eq!(names(dir.path()), ["My File.txt", "Other File.txt", "my-file.TXT"]);
+}
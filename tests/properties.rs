@@ -0,0 +1,57 @@
+//! Invariants that should hold for any name. Runs 1 000 cases per property, or 10 000 when `CI` is set.
+
+use dashify::{dashify, is_dashified, DashifyOptions};
+use proptest::prelude::*;
+
+fn config() -> ProptestConfig {
+    let cases = if std::env::var_os("CI").is_some() { 10_000 } else { 1_000 };
+    ProptestConfig::with_cases(cases)
+}
+
+fn options() -> impl Strategy<Value = DashifyOptions> {
+    any::<bool>().prop_map(|force_dash| DashifyOptions {
+        force_dash,
+        ..DashifyOptions::default()
+    })
+}
+
+proptest! {
+    #![proptest_config(config())]
+
+    #[test]
+    fn dashify_is_idempotent(name in "[ -~]{0,64}", options in options()) {
+        if let Ok(once) = dashify(&name, &options) {
+            prop_assert_eq!(dashify(&once, &options).ok(), Some(once));
+        }
+    }
+
+    #[test]
+    fn dashified_names_are_dashified(name in "[ -~]{0,64}", options in options()) {
+        if let Ok(dashified) = dashify(&name, &options) {
+            prop_assert!(is_dashified(&dashified, &options), "{:?} -> {:?}", name, dashified);
+        }
+    }
+
+    /// Splitting adds at most one separator between each pair of characters.
+    #[test]
+    fn dashify_at_most_doubles_the_length(name in "[ -~]{0,64}", options in options()) {
+        if let Ok(dashified) = dashify(&name, &options) {
+            prop_assert!(dashified.len() <= 2 * name.len(), "{:?} -> {:?}", name, dashified);
+        }
+    }
+}
+
+// Counterexamples proptest has found, kept as plain tests so they run on every `cargo test`.
+
+#[test]
+fn dots_brought_together_by_dropped_separators_collapse() {
+    let options = DashifyOptions::default();
+    assert_eq!(dashify("A. .0:", &options).unwrap(), "a.0");
+}
+
+#[test]
+fn removed_tilde_does_not_join_words() {
+    let options = DashifyOptions::default();
+    let once = dashify("0~A/", &options).unwrap();
+    assert_eq!(dashify(&once, &options).unwrap(), once);
+}
@@ -0,0 +1,73 @@
+use dashify::transform::collapse_mixed_separators;
+use dashify::{dashify, DashifyOptions};
+
+#[test]
+fn leading_runs_with_a_dash_collapse_to_a_dash() {
+    for run in ["-", "--", "_-", "-_", "_-_", "-_-", "__-", "-__"] {
+        assert_eq!(collapse_mixed_separators(&format!("{run}hello")), "-hello", "run {run:?}");
+    }
+}
+
+#[test]
+fn leading_runs_of_underscores_collapse_to_one_underscore() {
+    assert_eq!(collapse_mixed_separators("_hello"), "_hello");
+    assert_eq!(collapse_mixed_separators("__hello"), "_hello");
+    assert_eq!(collapse_mixed_separators("___hello"), "_hello");
+}
+
+#[test]
+fn runs_collapse_the_same_anywhere() {
+    assert_eq!(collapse_mixed_separators("hello_-"), "hello-");
+    assert_eq!(collapse_mixed_separators("hello__"), "hello_");
+    assert_eq!(collapse_mixed_separators("a-_b"), "a-b");
+    assert_eq!(collapse_mixed_separators("a__b"), "a_b");
+    assert_eq!(collapse_mixed_separators("-_a_-_b_-"), "-a-b-");
+}
+
+#[test]
+fn collapsing_is_all_that_happens() {
+    assert_eq!(collapse_mixed_separators(""), "");
+    assert_eq!(collapse_mixed_separators("Hello World"), "Hello World");
+    assert_eq!(collapse_mixed_separators("a.-b"), "a.-b");
+}
+
+#[test]
+fn dashify_trims_what_is_left_of_leading_runs() {
+    let options = DashifyOptions::default();
+    for name in ["_-hello.txt", "-_hello.txt", "__hello.txt", "--hello.txt", "_-_hello.txt"] {
+        assert_eq!(dashify(name, &options).unwrap(), "hello.txt", "name {name:?}");
+    }
+}
+
+fn dashify_with_separator(name: &str, separator: char, force_dash: bool) -> String {
+    let options = DashifyOptions {
+        separator,
+        force_dash,
+        ..DashifyOptions::default()
+    };
+    dashify(name, &options).unwrap()
+}
+
+#[test]
+fn underscore_separator_joins_words_with_underscores() {
+    assert_eq!(dashify_with_separator("My File.txt", '_', false), "my_file.txt");
+    assert_eq!(dashify_with_separator("myFileName.txt", '_', false), "my_file_name.txt");
+    assert_eq!(dashify_with_separator("already-dashed.txt", '_', false), "already_dashed.txt");
+    assert_eq!(dashify_with_separator("a - b.txt", '_', false), "a_b.txt");
+    assert_eq!(dashify_with_separator("Version 1.2 Notes.md", '_', false), "version_1.2_notes.md");
+}
+
+#[test]
+fn dot_separator_joins_words_with_dots() {
+    assert_eq!(dashify_with_separator("My File.txt", '.', false), "my.file.txt");
+    assert_eq!(dashify_with_separator("myFileName.txt", '.', false), "my.file.name.txt");
+    assert_eq!(dashify_with_separator("already-dashed.txt", '.', false), "already.dashed.txt");
+    assert_eq!(dashify_with_separator(".hidden File", '.', false), ".hidden.file");
+}
+
+#[test]
+fn underscores_are_kept_unless_force_dash() {
+    assert_eq!(dashify_with_separator("snake_case.txt", '.', false), "snake_case.txt");
+    assert_eq!(dashify_with_separator("snake_case.txt", '.', true), "snake.case.txt");
+    assert_eq!(dashify_with_separator("snake_case.txt", '_', true), "snake_case.txt");
+}
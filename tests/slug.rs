@@ -0,0 +1,70 @@
+use dashify::test_helpers::{assert_dashified_is_valid_for, Target};
+use dashify::{dashify_k8s, dashify_slug, DashifyError, DashifyOptions};
+
+fn slug(s: &str) -> String {
+    dashify_slug(s, &DashifyOptions::default()).unwrap()
+}
+
+#[test]
+fn dots_separate_words() {
+    assert_eq!(slug("foo.bar.baz"), "foo-bar-baz");
+    assert_eq!(slug("archive.tar.gz"), "archive-tar-gz");
+    assert_eq!(slug("...dots..."), "dots");
+}
+
+#[test]
+fn underscores_and_punctuation() {
+    assert_eq!(slug("my_app.v2"), "my-app-v-2");
+    assert_eq!(slug("Hello, World! (2024)"), "hello-world-2024");
+}
+
+#[test]
+fn non_ascii_letters_are_dropped_unless_transliterated() {
+    assert_eq!(slug("Café Menu"), "caf-menu");
+    assert_eq!(slug("Ünïcödé Service"), "ncd-service");
+
+    let transliterate = DashifyOptions {
+        transliterate: true,
+        ..DashifyOptions::default()
+    };
+    assert_eq!(dashify_slug("naïve_café.v2", &transliterate).unwrap(), "naive-cafe-v-2");
+    assert_eq!(dashify_slug("日本語", &transliterate).unwrap(), "ri-ben-yu");
+}
+
+#[test]
+fn nothing_left_is_an_error() {
+    assert!(matches!(dashify_slug("日本語", &DashifyOptions::default()), Err(DashifyError::EmptyResult { .. })));
+    assert!(matches!(dashify_k8s("!!!"), Err(DashifyError::EmptyResult { .. })));
+}
+
+#[test]
+fn k8s_names_split_dots_and_underscores() {
+    assert_eq!(dashify_k8s("my_service.v2.yaml").unwrap(), "my-service-v-2-yaml");
+    assert_eq!(dashify_k8s("-Leading And Trailing-").unwrap(), "leading-and-trailing");
+    assert_eq!(dashify_k8s("Ünïcödé Service").unwrap(), "ncd-service");
+}
+
+#[test]
+fn long_k8s_names_are_cut_at_a_dash() {
+    let name = "The Quick Brown Fox Jumps Over The Lazy Dog While The Cat Watches From The Windowsill.yaml";
+    let k8s = dashify_k8s(name).unwrap();
+    assert_eq!(k8s, "the-quick-brown-fox-jumps-over-the-lazy-dog-while-the-cat");
+    assert!(k8s.len() <= 63);
+
+    let k8s = dashify_k8s(&"a".repeat(100)).unwrap();
+    assert_eq!(k8s, "a".repeat(63));
+}
+
+#[test]
+fn k8s_options_give_valid_k8s_names() {
+    let options = DashifyOptions::for_k8s();
+    for name in ["my_service.v2.yaml", "Ünïcödé Service", "--Edge--Case--", &"Long Name ".repeat(10)] {
+        assert_dashified_is_valid_for(name, &options, Target::K8sName);
+    }
+}
+
+#[test]
+#[should_panic(expected = "\"Archive.TAR.GZ\" dashified to \"archive.tar.gz\", not a valid Slug")]
+fn names_with_extensions_are_not_slugs() {
+    assert_dashified_is_valid_for("Archive.TAR.GZ", &DashifyOptions::default(), Target::Slug);
+}
@@ -0,0 +1,62 @@
+use dashify::{count_bytes_saved, BatchRenameResult, ProcessingStats, RenameOperation};
+
+#[test]
+fn bytes_saved_sums_the_change_in_name_length() {
+    let ops = [
+        RenameOperation::new("dir/My  File.txt", "dir/my-file.txt"),
+        RenameOperation::new("dir/Copy (2).txt", "dir/copy-2.txt"),
+    ];
+    assert_eq!(count_bytes_saved(&ops), 3);
+    assert_eq!(count_bytes_saved(&[]), 0);
+}
+
+#[test]
+fn longer_names_count_as_negative_savings() {
+    let ops = [RenameOperation::new("someCamelCase.txt", "some-camel-case.txt")];
+    assert_eq!(count_bytes_saved(&ops), -2);
+}
+
+#[test]
+fn only_the_file_name_counts() {
+    let ops = [RenameOperation::new("a/very/long/dir/My File.txt", "my-file.txt")];
+    assert_eq!(count_bytes_saved(&ops), 0);
+}
+
+#[test]
+fn stats_summary_includes_bytes_saved() {
+    let mut stats = ProcessingStats::default();
+    stats.record(&RenameOperation::new("My  File.txt", "my-file.txt"));
+    stats.record(&RenameOperation::new("clean.txt", "clean.txt"));
+    stats.record_error("locked.txt", "permission denied");
+    assert_eq!(stats.bytes_saved, 1);
+    assert_eq!(
+        stats.to_string(),
+        "Processed 3 files: 1 renamed, 1 unchanged, 1 errors; names are 1 bytes shorter"
+    );
+
+    stats.record(&RenameOperation::new("aLongCamelCaseName", "a-long-camel-case-name"));
+    assert!(stats.to_string().ends_with("names are 3 bytes longer"));
+}
+
+#[test]
+fn batch_result_counts_skipped_and_renumbered_files() {
+    let mut result = BatchRenameResult::default();
+    result.record(&RenameOperation::new("My File.txt", "my-file.txt"));
+    result.record(&RenameOperation::new("clean.txt", "clean.txt"));
+    result.record_skipped("Clean.txt");
+    result.record_renumbered(&RenameOperation::new("MY FILE.txt", "my-file-2.txt"));
+    assert_eq!(result.processed(), 4);
+    assert_eq!(result.renamed, 2);
+    assert_eq!(result.unchanged, 1);
+    assert_eq!(
+        result.to_string(),
+        "Processed 4 files: 2 renamed (1 renumbered), 1 unchanged, 1 skipped, 0 errors"
+    );
+
+    let mut merged = BatchRenameResult::default();
+    merged.record_error("locked.txt", "permission denied");
+    merged.merge(result.clone());
+    assert_eq!(merged.skipped, result.skipped);
+    assert_eq!(merged.renumbered, result.renumbered);
+    assert_eq!(merged.processed(), 5);
+}
@@ -0,0 +1,45 @@
+use dashify::{dashify, DashifyError, DashifyOptions, TildeHandling};
+
+fn dashify_with(name: &str, tilde_handling: TildeHandling) -> Result<String, DashifyError> {
+    let options = DashifyOptions {
+        tilde_handling,
+        ..DashifyOptions::default()
+    };
+    dashify(name, &options)
+}
+
+#[test]
+fn tildes_are_removed_by_default() {
+    assert_eq!(DashifyOptions::default().tilde_handling, TildeHandling::Remove);
+    assert_eq!(dashify_with("file~.txt", TildeHandling::Remove).unwrap(), "file.txt");
+    assert_eq!(dashify_with("~file.txt", TildeHandling::Remove).unwrap(), "file.txt");
+    assert_eq!(dashify_with("file.txt~", TildeHandling::Remove).unwrap(), "file.txt");
+    assert_eq!(dashify_with("my~file.txt", TildeHandling::Remove).unwrap(), "myfile.txt");
+}
+
+#[test]
+fn preserve_keeps_tildes() {
+    assert_eq!(dashify_with("file~.txt", TildeHandling::Preserve).unwrap(), "file~.txt");
+    assert_eq!(dashify_with("~file.txt", TildeHandling::Preserve).unwrap(), "~file.txt");
+    assert_eq!(dashify_with("My~File.txt", TildeHandling::Preserve).unwrap(), "my~file.txt");
+}
+
+#[test]
+fn to_hyphen_turns_tildes_into_separators() {
+    assert_eq!(dashify_with("my~file.txt", TildeHandling::ToHyphen).unwrap(), "my-file.txt");
+    // a hyphen at either end of the stem is trimmed like any other separator
+    assert_eq!(dashify_with("file~.txt", TildeHandling::ToHyphen).unwrap(), "file.txt");
+    assert_eq!(dashify_with("~file.txt", TildeHandling::ToHyphen).unwrap(), "file.txt");
+}
+
+#[test]
+fn a_lone_tilde_leaves_nothing() {
+    for tilde_handling in [TildeHandling::Remove, TildeHandling::ToHyphen] {
+        assert!(matches!(dashify_with("~", tilde_handling), Err(DashifyError::EmptyResult { .. })));
+    }
+}
+
+#[test]
+fn semver_prerelease_tildes_are_left_alone() {
+    assert_eq!(dashify_with("1.0.0~rc1.txt", TildeHandling::Remove).unwrap(), "1.0.0~rc1.txt");
+}
@@ -0,0 +1,82 @@
+use dashify::{dashify, DashifyError, DashifyOptions};
+
+fn dashify_with_max_words(name: &str, max_stem_words: usize) -> Result<String, DashifyError> {
+    let options = DashifyOptions {
+        max_stem_words: Some(max_stem_words),
+        ..DashifyOptions::default()
+    };
+    dashify(name, &options)
+}
+
+#[test]
+fn long_pascal_case_names_keep_their_first_words() {
+    let name = "ConsiderationsWhileProjectPlanningForTheLongTermSuccessOfTheOrganizationAndItsPeople.doc";
+    assert_eq!(dashify_with_max_words(name, 5).unwrap(), "considerations-while-project-planning-for.doc");
+}
+
+#[test]
+fn words_are_kept_whole() {
+    assert_eq!(dashify_with_max_words("Chapter 12 Of The Long Book.pdf", 5).unwrap(), "chapter-12-of-the-long.pdf");
+    assert_eq!(dashify_with_max_words("one_two_three_four_five_six.txt", 5).unwrap(), "one_two_three_four_five.txt");
+}
+
+#[test]
+fn short_names_are_not_affected() {
+    assert_eq!(dashify_with_max_words("Short Name.txt", 5).unwrap(), "short-name.txt");
+    assert_eq!(dashify_with_max_words("already-short.txt", 5).unwrap(), "already-short.txt");
+}
+
+#[test]
+fn compound_extensions_are_kept() {
+    assert_eq!(dashify_with_max_words("a-b-c-d-e-f.tar.gz", 5).unwrap(), "a-b-c-d-e.tar.gz");
+}
+
+#[test]
+fn zero_words_leaves_nothing() {
+    assert!(matches!(dashify_with_max_words("x.doc", 0), Err(DashifyError::EmptyResult { .. })));
+}
+
+fn dashify_with_max_length(name: &str, max_length: usize) -> String {
+    let options = DashifyOptions {
+        max_length: Some(max_length),
+        ..DashifyOptions::default()
+    };
+    dashify(name, &options).unwrap()
+}
+
+#[test]
+fn a_limit_mid_word_cuts_at_the_last_separator_before_it() {
+    assert_eq!(dashify_with_max_length("Quarterly Financial Report.pdf", 12), "quarterly.pdf");
+    assert_eq!(dashify_with_max_length("Quarterly Financial Report.pdf", 25), "quarterly-financial.pdf");
+    assert_eq!(dashify_with_max_length("one_two_three.txt", 8), "one_two.txt");
+}
+
+#[test]
+fn a_limit_at_a_word_boundary_keeps_the_whole_word() {
+    assert_eq!(dashify_with_max_length("Quarterly Financial Report.pdf", 9), "quarterly.pdf");
+    assert_eq!(dashify_with_max_length("Quarterly Financial Report.pdf", 19), "quarterly-financial.pdf");
+}
+
+#[test]
+fn a_limit_just_past_a_separator_drops_it() {
+    assert_eq!(dashify_with_max_length("Quarterly Financial Report.pdf", 10), "quarterly.pdf");
+    assert_eq!(dashify_with_max_length("one_two_three.txt", 4), "one.txt");
+}
+
+#[test]
+fn a_word_longer_than_the_limit_is_cut_mid_word() {
+    assert_eq!(dashify_with_max_length("Supercalifragilistic.txt", 8), "supercal.txt");
+    assert_eq!(dashify_with_max_length("Quarterly Financial Report.pdf", 5), "quart.pdf");
+}
+
+#[test]
+fn stems_within_the_limit_are_not_affected() {
+    assert_eq!(dashify_with_max_length("Short Name.txt", 10), "short-name.txt");
+    assert_eq!(dashify_with_max_length("short.txt", 64), "short.txt");
+}
+
+#[test]
+fn the_extension_does_not_count() {
+    assert_eq!(dashify_with_max_length("abc-def.tar.gz", 5), "abc.tar.gz");
+    assert_eq!(dashify_with_max_length("My Notes.markdown", 8), "my-notes.markdown");
+}
@@ -0,0 +1,54 @@
+#![cfg(feature = "regex-patterns")]
+
+use dashify::{dashify, validate_options, DashifyError, DashifyOptions};
+use regex::Regex;
+use std::path::Path;
+
+#[test]
+fn leave_alone_patterns_keep_matching_names() {
+    let options = DashifyOptions::builder()
+        .leave_alone_pattern(Regex::new(r"^[0-9a-f]{40}\.blob$").unwrap())
+        .build()
+        .unwrap();
+    let hash = "3f786850e387550fdab836ed7e6dc881de23001b.blob";
+    assert_eq!(dashify(hash, &options).unwrap(), hash);
+    assert_eq!(dashify("My Blob.blob", &options).unwrap(), "my-blob.blob");
+}
+
+#[test]
+fn preserve_patterns_match_the_whole_name() {
+    let options = DashifyOptions::builder().preserve_pattern("Makefile|Dockerfile").build().unwrap();
+    assert_eq!(dashify("Dockerfile", &options).unwrap(), "Dockerfile");
+    assert_eq!(dashify("Dockerfile Old", &options).unwrap(), "dockerfile-old");
+}
+
+#[test]
+fn invalid_preserve_patterns_are_reported() {
+    let options = DashifyOptions {
+        preserve_patterns: vec!["(unclosed".to_string()],
+        ..DashifyOptions::default()
+    };
+    assert!(matches!(validate_options(&options), Err(DashifyError::InvalidPattern { .. })));
+    assert!(matches!(dashify("Any Name", &options), Err(DashifyError::InvalidPattern { .. })));
+}
+
+#[test]
+fn compiled_options_dashify_like_the_options() {
+    let options = DashifyOptions::builder().preserve_pattern(r"go\.(mod|sum)").force_dash(true).build().unwrap();
+    let compiled = validate_options(&options).unwrap();
+    for name in ["go.mod", "go.sum", "My_File.txt", "go.work"] {
+        assert_eq!(compiled.dashify(name).unwrap(), dashify(name, &options).unwrap());
+    }
+    assert!(compiled.is_dashified("go.mod"));
+    assert!(compiled.force_dash);
+
+    let owned = DashifyOptions::builder().preserve_pattern("Makefile").build_compiled().unwrap();
+    assert_eq!(owned.dashify_path(Path::new("src/Makefile")).unwrap(), Path::new("src/Makefile"));
+    assert_eq!(owned.dashify_path(Path::new("src/My File")).unwrap(), Path::new("src/my-file"));
+}
+
+#[test]
+fn invalid_patterns_fail_to_compile() {
+    let builder = DashifyOptions::builder().preserve_pattern("(unclosed");
+    assert!(matches!(builder.build_compiled(), Err(DashifyError::InvalidPattern { .. })));
+}
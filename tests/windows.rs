@@ -0,0 +1,29 @@
+use dashify::normalize_windows_filename;
+
+#[test]
+fn reserved_device_names_get_win_appended_to_the_stem() {
+    assert_eq!(normalize_windows_filename("con.txt"), "con_win.txt");
+    assert_eq!(normalize_windows_filename("CON"), "CON_win");
+    assert_eq!(normalize_windows_filename("nul"), "nul_win");
+    assert_eq!(normalize_windows_filename("LPT1.log"), "LPT1_win.log");
+}
+
+#[test]
+fn the_stem_is_everything_before_the_first_dot() {
+    assert_eq!(normalize_windows_filename("aux.tar.gz"), "aux_win.tar.gz");
+    assert_eq!(normalize_windows_filename("Aux.Tar.Gz"), "aux_win.tar.gz");
+    assert_eq!(normalize_windows_filename("console.txt"), "console.txt");
+}
+
+#[test]
+fn illegal_characters_become_dashes() {
+    assert_eq!(normalize_windows_filename("a<b>c:d\"e|f?g*h.txt"), "a-b-c-d-e-f-g-h.txt");
+    assert_eq!(normalize_windows_filename("What? Now*.txt"), "what-now.txt");
+}
+
+#[test]
+fn trailing_dots_and_spaces_are_dropped() {
+    assert_eq!(normalize_windows_filename("Notes. "), "notes");
+    assert_eq!(normalize_windows_filename("report.txt..."), "report.txt");
+    assert_eq!(normalize_windows_filename("..."), "_win");
+}